@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 
+use super::report::ChainReport;
+
+/// The largest joltage step an adapter accepts unless a device says otherwise.
+const DEFAULT_TOLERANCE: i32 = 3;
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
 struct Adapter {
     joltage_rating: i32,
@@ -17,20 +22,80 @@ impl Adapter {
     }
 }
 
+/// Lazily walks every chain from the smallest to the largest joltage rating, depth first.
+pub struct Arrangements {
+    joltage_ratings: Vec<i32>,
+    tolerance: i32,
+    stack: Vec<(usize, usize)>,
+}
+
+impl Iterator for Arrangements {
+    type Item = Vec<i32>;
+
+    fn next(&mut self) -> Option<Vec<i32>> {
+        let last_index = self.joltage_ratings.len().checked_sub(1)?;
+
+        while let Some(&(index, next_index)) = self.stack.last() {
+            if index == last_index {
+                let arrangement = self
+                    .stack
+                    .iter()
+                    .map(|&(index, _)| self.joltage_ratings[index])
+                    .collect();
+
+                self.stack.pop();
+
+                return Some(arrangement);
+            }
+
+            let joltage_difference = self
+                .joltage_ratings
+                .get(next_index)
+                .map(|joltage_rating| joltage_rating - self.joltage_ratings[index]);
+
+            match joltage_difference {
+                Some(difference) if difference <= self.tolerance => {
+                    self.stack.last_mut().unwrap().1 += 1;
+
+                    if 1 <= difference {
+                        self.stack.push((next_index, next_index + 1));
+                    }
+                }
+                _ => {
+                    self.stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+}
+
 #[derive(Debug, PartialEq)]
 struct AdapterArray {
     adapters: Vec<Adapter>,
+    tolerance: i32,
 }
 
 impl AdapterArray {
-    fn new(joltage_ratings: &[String]) -> AdapterArray {
-        let temp_vec: Vec<Adapter> = joltage_ratings.iter().map(|s| Adapter::new(s)).collect();
+    fn new(joltage_ratings: &[String], tolerance: i32) -> AdapterArray {
+        let mut adapter_array = AdapterArray {
+            adapters: Vec::with_capacity(joltage_ratings.len()),
+            tolerance,
+        };
+
+        for joltage_rating in joltage_ratings {
+            adapter_array.insert_adapter(Adapter::new(joltage_rating));
+        }
 
-        AdapterArray { adapters: temp_vec }
+        adapter_array
     }
 
+    /// Adapters with the same joltage rating are interchangeable, so only the first one is kept.
     fn insert_adapter(&mut self, adapter: Adapter) {
-        self.adapters.push(adapter);
+        if !self.adapters.contains(&adapter) {
+            self.adapters.push(adapter);
+        }
     }
 
     fn get_joltage_differences_using_all_adapters(&self) -> HashMap<i32, i32> {
@@ -41,7 +106,7 @@ impl AdapterArray {
         let mut joltage_differences = HashMap::new();
 
         let mut current_joltage = adapters_sorted
-            .get(0)
+            .first()
             .unwrap_or(&Adapter::new("0"))
             .get_joltage_rating();
 
@@ -56,44 +121,107 @@ impl AdapterArray {
         joltage_differences
     }
 
-    fn number_of_unique_adapter_combinations(&self) -> u64 {
-        let mut adapters_sorted = self.adapters.clone();
+    fn number_of_unique_adapter_combinations(&self) -> Result<u64, String> {
+        self.check_chain()?;
 
-        adapters_sorted.sort();
+        let joltage_ratings = self.get_sorted_joltage_ratings();
 
         let mut combinations_up_to: HashMap<i32, u64> = HashMap::new();
 
-        let largest_joltage_rating = self.get_largest_joltage_rating();
-        let smallest_joltage_rating = self.get_smallest_joltage_rating();
+        combinations_up_to.insert(self.get_smallest_joltage_rating(), 1);
 
-        combinations_up_to.insert(smallest_joltage_rating, 1);
+        for &joltage_rating in joltage_ratings.iter().skip(1) {
+            let combinations = (1..=self.tolerance)
+                .map(|step| {
+                    combinations_up_to
+                        .get(&(joltage_rating - step))
+                        .unwrap_or(&0)
+                })
+                .try_fold(0u64, |sum, &combinations| sum.checked_add(combinations))
+                .ok_or("Too Many Arrangements To Count")?;
 
-        for adapter in &adapters_sorted[1..] {
-            let one_joltage_rating_behind = adapter.get_joltage_rating() - 1;
-            let two_joltage_rating_behind = adapter.get_joltage_rating() - 2;
-            let three_joltage_rating_behind = adapter.get_joltage_rating() - 3;
-
-            let combinations_up_to_one_behind = *combinations_up_to
-                .get(&one_joltage_rating_behind)
-                .unwrap_or(&0);
-            let combinations_up_to_two_behind = *combinations_up_to
-                .get(&two_joltage_rating_behind)
-                .unwrap_or(&0);
-            let combinations_up_to_three_behind = *combinations_up_to
-                .get(&three_joltage_rating_behind)
-                .unwrap_or(&0);
-
-            combinations_up_to.insert(
-                adapter.get_joltage_rating(),
-                combinations_up_to_one_behind
-                    + combinations_up_to_two_behind
-                    + combinations_up_to_three_behind,
-            );
+            combinations_up_to.insert(joltage_rating, combinations);
         }
 
-        *combinations_up_to
-            .get(&largest_joltage_rating)
-            .unwrap_or(&0)
+        Ok(*combinations_up_to
+            .get(&self.get_largest_joltage_rating())
+            .unwrap_or(&0))
+    }
+
+    fn arrangements(&self) -> Arrangements {
+        let joltage_ratings = self.get_sorted_joltage_ratings();
+
+        let stack = if joltage_ratings.is_empty() {
+            Vec::new()
+        } else {
+            vec![(0, 1)]
+        };
+
+        Arrangements {
+            joltage_ratings,
+            tolerance: self.tolerance,
+            stack,
+        }
+    }
+
+    /// The chain with the fewest adapters, found by always stepping to the highest joltage
+    /// rating still within the tolerance.
+    fn find_minimal_chain(&self) -> Result<Vec<i32>, String> {
+        self.check_chain()?;
+
+        let joltage_ratings = self.get_sorted_joltage_ratings();
+
+        let mut chain: Vec<i32> = joltage_ratings.first().copied().into_iter().collect();
+        let mut index = 0;
+
+        while index + 1 < joltage_ratings.len() {
+            let current_joltage_rating = joltage_ratings[index];
+
+            index = (index + 1..joltage_ratings.len())
+                .take_while(|&next| {
+                    joltage_ratings[next] - current_joltage_rating <= self.tolerance
+                })
+                .last()
+                .unwrap();
+
+            chain.push(joltage_ratings[index]);
+        }
+
+        Ok(chain)
+    }
+
+    /// Pairs of neighbouring joltage ratings that are further apart than the tolerance.
+    fn find_gaps(&self) -> Vec<(i32, i32)> {
+        self.get_sorted_joltage_ratings()
+            .windows(2)
+            .filter(|pair| self.tolerance < pair[1] - pair[0])
+            .map(|pair| (pair[0], pair[1]))
+            .collect()
+    }
+
+    fn check_chain(&self) -> Result<(), String> {
+        match self.find_gaps().first() {
+            Some((lower, upper)) => Err(format!(
+                "Gap Of {} Jolts Between {} And {} Exceeds The Tolerance Of {}",
+                upper - lower,
+                lower,
+                upper,
+                self.tolerance
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn get_sorted_joltage_ratings(&self) -> Vec<i32> {
+        let mut joltage_ratings: Vec<i32> = self
+            .adapters
+            .iter()
+            .map(|adapter| adapter.get_joltage_rating())
+            .collect();
+
+        joltage_ratings.sort_unstable();
+
+        joltage_ratings
     }
 
     fn get_largest_joltage_rating(&self) -> i32 {
@@ -113,6 +241,11 @@ impl AdapterArray {
     }
 }
 
+/// The outlet, the adapters and the built-in adapter of a device.
+///
+/// Adapters that share a joltage rating are interchangeable, so only one adapter per rating
+/// is kept. Repeated ratings in the input neither add arrangements nor joltage differences of
+/// zero.
 #[derive(Debug, PartialEq)]
 pub struct Device {
     adapter_array: AdapterArray,
@@ -121,7 +254,7 @@ pub struct Device {
 
 impl Device {
     pub fn new(adapters_joltage_rating: &[String], built_in_joltage_difference: i32) -> Device {
-        let mut adapter_array = AdapterArray::new(adapters_joltage_rating);
+        let mut adapter_array = AdapterArray::new(adapters_joltage_rating, DEFAULT_TOLERANCE);
 
         adapter_array.insert_adapter(Adapter::new("0"));
 
@@ -143,9 +276,33 @@ impl Device {
             .get_joltage_differences_using_all_adapters()
     }
 
-    pub fn number_of_unique_adapter_combinations(&self) -> u64 {
+    pub fn get_report(&self) -> ChainReport {
+        ChainReport::new(
+            &self.adapter_array.get_sorted_joltage_ratings(),
+            self.adapter_array.tolerance,
+        )
+    }
+
+    /// Sets the largest joltage step between two connected adapters.
+    pub fn with_tolerance(mut self, tolerance: i32) -> Device {
+        self.adapter_array.tolerance = tolerance;
+
+        self
+    }
+
+    pub fn number_of_unique_adapter_combinations(&self) -> Result<u64, String> {
         self.adapter_array.number_of_unique_adapter_combinations()
     }
+
+    /// Every chain from the outlet to the device, produced one at a time.
+    pub fn arrangements(&self) -> Arrangements {
+        self.adapter_array.arrangements()
+    }
+
+    /// A chain from the outlet to the device that uses as few adapters as possible.
+    pub fn find_minimal_chain(&self) -> Result<Vec<i32>, String> {
+        self.adapter_array.find_minimal_chain()
+    }
 }
 
 #[cfg(test)]
@@ -159,7 +316,7 @@ mod tests {
     fn test_adapter_array_new() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
 
-        let result = AdapterArray::new(&input);
+        let result = AdapterArray::new(&input, 3);
 
         let expected = AdapterArray {
             adapters: vec![
@@ -175,6 +332,7 @@ mod tests {
                 Adapter { joltage_rating: 12 },
                 Adapter { joltage_rating: 4 },
             ],
+            tolerance: 3,
         };
 
         assert_eq!(result, expected);
@@ -184,7 +342,7 @@ mod tests {
     fn test_adapter_array_get_joltage_differences_using_all_adapters_simple() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
 
-        let adapter_array = AdapterArray::new(&input);
+        let adapter_array = AdapterArray::new(&input, 3);
 
         let result = adapter_array.get_joltage_differences_using_all_adapters();
 
@@ -197,7 +355,7 @@ mod tests {
     fn test_adapter_array_get_joltage_differences_using_all_adapters_complex() {
         let input = to_string_vector("test_inputs/day_10.txt").unwrap();
 
-        let adapter_array = AdapterArray::new(&input);
+        let adapter_array = AdapterArray::new(&input, 3);
 
         let result = adapter_array.get_joltage_differences_using_all_adapters();
 
@@ -210,11 +368,11 @@ mod tests {
     fn test_adapter_array_number_of_unique_adapter_combinations_simple() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
 
-        let adapter_array = AdapterArray::new(&input);
+        let adapter_array = AdapterArray::new(&input, 3);
 
         let result = adapter_array.number_of_unique_adapter_combinations();
 
-        let expected = 8;
+        let expected = Ok(8);
 
         assert_eq!(result, expected);
     }
@@ -240,8 +398,110 @@ mod tests {
 
         let result = device.number_of_unique_adapter_combinations();
 
-        let expected = 19208;
+        let expected = Ok(19208);
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_device_number_of_unique_adapter_combinations_tolerance() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let result_1 = Device::new(&input, 3)
+            .with_tolerance(4)
+            .number_of_unique_adapter_combinations();
+        let result_2 = Device::new(&input, 3)
+            .with_tolerance(2)
+            .number_of_unique_adapter_combinations();
+
+        assert_eq!(result_1, Ok(170));
+        assert_eq!(
+            result_2,
+            Err(String::from(
+                "Gap Of 3 Jolts Between 1 And 4 Exceeds The Tolerance Of 2"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_device_arrangements() {
+        let input: Vec<String> = vec!["1", "2", "4", "5"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let device = Device::new(&input, 3);
+
+        let result: Vec<Vec<i32>> = device.arrangements().collect();
+
+        let expected = vec![
+            vec![0, 1, 2, 4, 5, 8],
+            vec![0, 1, 2, 5, 8],
+            vec![0, 1, 4, 5, 8],
+            vec![0, 2, 4, 5, 8],
+            vec![0, 2, 5, 8],
+        ];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_device_arrangements_count() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let device = Device::new(&input, 3);
+
+        let result = device.arrangements().count() as u64;
+
+        assert_eq!(Ok(result), device.number_of_unique_adapter_combinations());
+    }
+
+    #[test]
+    fn test_device_duplicate_joltage_ratings() {
+        let input: Vec<String> = vec!["1", "2", "2", "4", "0", "5", "1"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let device = Device::new(&input, 3);
+
+        let result_1 = device.arrangements().count() as u64;
+        let result_2 = device.number_of_unique_adapter_combinations();
+        let result_3 = device.get_joltage_differences_using_all_adapters();
+        let result_4 = device.adapter_array.get_sorted_joltage_ratings();
+
+        let expected_3: HashMap<i32, i32> = vec![(1, 3), (2, 1), (3, 1)].into_iter().collect();
+
+        assert_eq!(result_1, 5);
+        assert_eq!(result_2, Ok(5));
+        assert_eq!(result_3, expected_3);
+        assert_eq!(result_4, vec![0, 1, 2, 4, 5, 8]);
+    }
+
+    #[test]
+    fn test_device_find_minimal_chain() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let result_1 = Device::new(&input, 3).find_minimal_chain();
+        let result_2 = Device::new(&input, 3)
+            .with_tolerance(1)
+            .find_minimal_chain();
+
+        let expected_1 = Ok(vec![0, 1, 4, 7, 10, 12, 15, 16, 19, 22]);
+
+        assert_eq!(result_1, expected_1);
+        assert!(result_2.is_err());
+    }
+
+    #[test]
+    fn test_device_get_report() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let report = Device::new(&input, 3).get_report();
+
+        assert!(report.is_valid());
+        assert_eq!(report.chain.first(), Some(&0));
+        assert_eq!(report.chain.last(), Some(&22));
+        assert_eq!(report.optional_adapters, vec![5, 6, 11]);
+    }
 }
@@ -1,6 +1,8 @@
 mod adapter;
+mod report;
 
 use crate::util::file_reader::to_string_vector;
+use crate::{get_i32_user_input, get_user_input, wants_extras};
 use adapter::Device;
 
 pub fn run_day_10() {
@@ -15,11 +17,54 @@ pub fn run_day_10() {
             let part_1 = device_joltage_differences.get(&1).unwrap_or(&0)
                 * device_joltage_differences.get(&3).unwrap_or(&0);
 
-            let part_2 = device.number_of_unique_adapter_combinations();
-
             println!("Day 10 Part 1: {:?}", part_1);
-            println!("Day 10 Part 2: {:?}", part_2);
+
+            match device.number_of_unique_adapter_combinations() {
+                Ok(part_2) => println!("Day 10 Part 2: {:?}", part_2),
+                Err(error) => println!("Day 10 Part 2: {}", error),
+            }
+
+            if !wants_extras() {
+                return;
+            }
+
+            print!("Print the chain report (0 = no, 1 = yes): ");
+
+            if get_user_input() == 1 {
+                println!("{}", device.get_report());
+            }
+
+            print!("Adapter joltage tolerance to analyse (0 = skip): ");
+
+            let tolerance = get_i32_user_input();
+
+            // The built-in adapter is rated one tolerance above the largest adapter, as it is
+            // three jolts above for the default tolerance.
+            if 0 < tolerance {
+                analyse_tolerance(&Device::new(&input_lines, tolerance).with_tolerance(tolerance));
+            }
         }
         Err(error) => println!("Error Parsing File: {:?}", error),
     };
 }
+
+fn analyse_tolerance(device: &Device) {
+    match device.number_of_unique_adapter_combinations() {
+        Ok(combinations) => println!("Arrangements: {}", combinations),
+        Err(error) => {
+            println!("{}", error);
+
+            return;
+        }
+    }
+
+    if let Ok(minimal_chain) = device.find_minimal_chain() {
+        println!("Minimal Chain: {:?}", minimal_chain);
+    }
+
+    print!("Number of arrangements to list: ");
+
+    for arrangement in device.arrangements().take(get_user_input() as usize) {
+        println!("{:?}", arrangement);
+    }
+}
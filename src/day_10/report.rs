@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Two neighbouring joltage ratings of the chain that are too far apart to connect.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvalidGap {
+    pub position: usize,
+    pub lower: i32,
+    pub upper: i32,
+}
+
+/// Explains a chain that uses every adapter, from the outlet to the device.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChainReport {
+    pub chain: Vec<i32>,
+    pub tolerance: i32,
+    pub histogram: BTreeMap<i32, usize>,
+    pub invalid_gaps: Vec<InvalidGap>,
+    pub mandatory_adapters: Vec<i32>,
+    pub optional_adapters: Vec<i32>,
+}
+
+impl ChainReport {
+    /// `chain` has to be sorted. An adapter is mandatory if its neighbours are too far apart
+    /// to connect without it; the outlet and the device always are. If the chain has invalid
+    /// gaps there are no arrangements at all, so no adapter is classified.
+    pub fn new(chain: &[i32], tolerance: i32) -> ChainReport {
+        let mut histogram = BTreeMap::new();
+        let mut invalid_gaps = Vec::new();
+
+        for (position, pair) in chain.windows(2).enumerate() {
+            let joltage_difference = pair[1] - pair[0];
+
+            *histogram.entry(joltage_difference).or_insert(0) += 1;
+
+            if !(1..=tolerance).contains(&joltage_difference) {
+                invalid_gaps.push(InvalidGap {
+                    position,
+                    lower: pair[0],
+                    upper: pair[1],
+                });
+            }
+        }
+
+        let mut mandatory_adapters = Vec::new();
+        let mut optional_adapters = Vec::new();
+
+        if invalid_gaps.is_empty() {
+            for (index, &joltage_rating) in chain.iter().enumerate() {
+                let is_mandatory = match (index.checked_sub(1), chain.get(index + 1)) {
+                    (Some(previous), Some(next)) => tolerance < next - chain[previous],
+                    _ => true,
+                };
+
+                if is_mandatory {
+                    mandatory_adapters.push(joltage_rating);
+                } else {
+                    optional_adapters.push(joltage_rating);
+                }
+            }
+        }
+
+        ChainReport {
+            chain: chain.to_vec(),
+            tolerance,
+            histogram,
+            invalid_gaps,
+            mandatory_adapters,
+            optional_adapters,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.invalid_gaps.is_empty()
+    }
+}
+
+impl fmt::Display for ChainReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Chain: {}", join(&self.chain))?;
+        writeln!(f, "Differences:")?;
+
+        for (joltage_difference, count) in &self.histogram {
+            writeln!(f, "  {} jolts: {}", joltage_difference, count)?;
+        }
+
+        if self.is_valid() {
+            writeln!(f, "Invalid gaps: none")?;
+            writeln!(f, "Mandatory adapters: {}", join(&self.mandatory_adapters))?;
+            write!(f, "Optional adapters: {}", join(&self.optional_adapters))
+        } else {
+            write!(f, "Invalid gaps (tolerance {}):", self.tolerance)?;
+
+            for gap in &self.invalid_gaps {
+                write!(
+                    f,
+                    "\n  {} -> {} ({} jolts) after position {}",
+                    gap.lower,
+                    gap.upper,
+                    gap.upper - gap.lower,
+                    gap.position
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn join(joltage_ratings: &[i32]) -> String {
+    let joltage_ratings: Vec<String> = joltage_ratings.iter().map(|j| j.to_string()).collect();
+
+    joltage_ratings.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CHAIN: [i32; 13] = [0, 1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19, 22];
+
+    #[test]
+    fn test_chain_report_new() {
+        let result = ChainReport::new(&TEST_CHAIN, 3);
+
+        let expected = ChainReport {
+            chain: TEST_CHAIN.to_vec(),
+            tolerance: 3,
+            histogram: vec![(1, 7), (3, 5)].into_iter().collect(),
+            invalid_gaps: Vec::new(),
+            mandatory_adapters: vec![0, 1, 4, 7, 10, 12, 15, 16, 19, 22],
+            optional_adapters: vec![5, 6, 11],
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_chain_report_invalid_gaps() {
+        let result = ChainReport::new(&[0, 1, 5, 6, 9], 3);
+
+        let expected_gaps = vec![InvalidGap {
+            position: 1,
+            lower: 1,
+            upper: 5,
+        }];
+
+        assert_eq!(result.invalid_gaps, expected_gaps);
+        assert!(!result.is_valid());
+        assert!(result.mandatory_adapters.is_empty());
+        assert_eq!(
+            result.to_string(),
+            "Chain: 0 1 5 6 9
+Differences:
+  1 jolts: 2
+  3 jolts: 1
+  4 jolts: 1
+Invalid gaps (tolerance 3):
+  1 -> 5 (4 jolts) after position 1"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::Path;
+
+/// All generations in one text, each preceded by a `generation N` header.
+pub fn to_text_frames(generations: &[String]) -> String {
+    generations
+        .iter()
+        .enumerate()
+        .map(|(generation, frame)| format!("generation {}\n{}\n", generation, frame))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Renders one text frame as a binary PPM image, drawing every object as a `scale` by `scale`
+/// square.
+pub fn to_ppm(frame: &str, scale: usize) -> Vec<u8> {
+    let rows: Vec<&str> = frame.lines().collect();
+
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    let height = rows.len();
+
+    let mut image = format!("P6\n{} {}\n255\n", width * scale, height * scale).into_bytes();
+
+    for row in &rows {
+        let mut pixel_row = Vec::with_capacity(width * scale * 3);
+
+        for col in 0..width {
+            let color = get_color(row.chars().nth(col).unwrap_or('.'));
+
+            for _ in 0..scale {
+                pixel_row.extend_from_slice(&color);
+            }
+        }
+
+        for _ in 0..scale {
+            image.extend_from_slice(&pixel_row);
+        }
+    }
+
+    image
+}
+
+/// Writes `frame_0000.ppm`, `frame_0001.ppm`, ... into `directory`, creating it if needed.
+pub fn write_ppm_frames(
+    generations: &[String],
+    directory: &Path,
+    scale: usize,
+) -> Result<(), String> {
+    fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+
+    for (generation, frame) in generations.iter().enumerate() {
+        let path = directory.join(format!("frame_{:04}.ppm", generation));
+
+        fs::write(&path, to_ppm(frame, scale)).map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn get_color(object: char) -> [u8; 3] {
+    match object {
+        'L' => [46, 160, 67],
+        '#' => [218, 54, 51],
+        'X' => [110, 110, 110],
+        '|' => [56, 108, 200],
+        _ => [24, 24, 24],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_text_frames() {
+        let generations = vec![String::from("L.\nLL"), String::from("#.\n##")];
+
+        let result = to_text_frames(&generations);
+
+        let expected = "generation 0\nL.\nLL\n\ngeneration 1\n#.\n##\n";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        let result = to_ppm("L#", 2);
+
+        let mut expected = b"P6\n4 2\n255\n".to_vec();
+
+        for _ in 0..2 {
+            expected.extend_from_slice(&[46, 160, 67, 46, 160, 67, 218, 54, 51, 218, 54, 51]);
+        }
+
+        assert_eq!(result, expected);
+    }
+}
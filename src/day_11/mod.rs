@@ -1,8 +1,18 @@
+mod frames;
+mod rules;
 mod seating_system;
 
+use std::fs;
+use std::path::Path;
+
 use crate::util::file_reader::to_string_vector;
+use crate::{get_i32_user_input, get_user_input, wants_extras};
+
+use rules::{Neighbourhood, SeatingRules};
 use seating_system::SeatingSystem;
 
+const STEP_LIMIT: usize = 10_000;
+
 pub fn run_day_11() {
     let file_input = to_string_vector("inputs/day_11.txt");
 
@@ -22,7 +32,137 @@ pub fn run_day_11() {
 
             println!("Day 11 Part 1: {}", part_1);
             println!("Day 11 Part 2: {}", part_2);
+
+            if !wants_extras() {
+                return;
+            }
+
+            let mut recorded_system = SeatingSystem::new(&input_lines).with_history();
+
+            recorded_system.simulate_until_stable_with_los();
+
+            export_generations(recorded_system.get_history());
+            inspect_generation(recorded_system.get_history());
+
+            if let Some(rules) = read_seating_rules() {
+                seating_system.reset();
+                let outcome = seating_system.simulate(&rules, Some(STEP_LIMIT));
+
+                println!(
+                    "Occupied seats: {} ({})",
+                    seating_system.number_of_occupied_seats(),
+                    outcome
+                );
+            }
         }
         Err(error) => println!("Error Parsing File: {:?}", error),
     };
 }
+
+fn read_seating_rules() -> Option<SeatingRules> {
+    print!(
+        "Neighbourhood for custom rules (0 = skip, 1 = adjacent, 2 = line of sight, 3 = radius, \
+         4 = knight moves): "
+    );
+
+    let neighbourhood = match get_user_input() {
+        1 => Neighbourhood::Adjacent,
+        2 => Neighbourhood::LineOfSight,
+        3 => {
+            print!("Radius: ");
+
+            loop {
+                match Neighbourhood::radius(get_i32_user_input()) {
+                    Ok(neighbourhood) => break neighbourhood,
+                    Err(error) => print!("{}. Radius: ", error),
+                }
+            }
+        }
+        4 => Neighbourhood::Custom(vec![
+            (1, -2),
+            (2, -1),
+            (2, 1),
+            (1, 2),
+            (-1, 2),
+            (-2, 1),
+            (-2, -1),
+            (-1, -2),
+        ]),
+        _ => return None,
+    };
+
+    print!("Occupied neighbours that make a person leave: ");
+
+    let vacate_threshold = get_user_input() as usize;
+
+    print!("Occupied neighbours a person still accepts: ");
+
+    let occupy_threshold = get_user_input() as usize;
+
+    Some(SeatingRules::new(neighbourhood, vacate_threshold).with_occupy_threshold(occupy_threshold))
+}
+
+fn export_generations(generations: &[String]) {
+    print!("Export the part 2 generations (0 = no, 1 = text frames, 2 = ppm frames): ");
+
+    let result = match get_user_input() {
+        1 => fs::create_dir_all("output")
+            .and_then(|_| {
+                fs::write(
+                    "output/day_11_frames.txt",
+                    frames::to_text_frames(generations),
+                )
+            })
+            .map_err(|error| error.to_string()),
+        2 => frames::write_ppm_frames(generations, Path::new("output/day_11"), 4),
+        _ => return,
+    };
+
+    match result {
+        Ok(()) => println!("Wrote {} generations to output/", generations.len()),
+        Err(error) => println!("Error Writing Frames: {}", error),
+    }
+}
+
+fn inspect_generation(generations: &[String]) {
+    print!(
+        "Inspect a part 2 generation (0 = skip, 1 to {}): ",
+        generations.len().saturating_sub(1)
+    );
+
+    let generation = get_user_input() as usize;
+
+    if generation == 0 || generations.len() <= generation {
+        return;
+    }
+
+    let to_lines = |frame: &str| -> Vec<String> { frame.lines().map(String::from).collect() };
+
+    let previous = SeatingSystem::new(&to_lines(&generations[generation - 1]));
+    let current = SeatingSystem::new(&to_lines(&generations[generation]));
+
+    match previous.diff(&current) {
+        Ok(diff) => println!("Changes in generation {}:\n{}", generation, diff),
+        Err(error) => println!("Error Comparing Generations: {}", error),
+    }
+
+    let mut snapshot = vec![format!("generation {}", generation)];
+
+    snapshot.extend(to_lines(&generations[generation]));
+
+    let mut resumed = SeatingSystem::new(&to_lines(&generations[0]));
+
+    match resumed.load_snapshot(&snapshot) {
+        Ok(()) => {
+            let outcome = resumed.simulate_until_stable_with_rules(&SeatingRules::line_of_sight());
+
+            println!(
+                "Resumed from generation {}: {} occupied seats ({})",
+                generation,
+                resumed.number_of_occupied_seats(),
+                outcome
+            );
+        }
+        Err(error) => println!("Error Loading Snapshot: {}", error),
+    }
+}
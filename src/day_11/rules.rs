@@ -0,0 +1,132 @@
+/// Which seats count as the neighbours of a seat.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Neighbourhood {
+    /// The eight surrounding locations.
+    Adjacent,
+    /// The first object that blocks the view in each of the eight directions.
+    LineOfSight,
+    /// Every location within `k` steps in any direction, diagonals included.
+    Radius(i32),
+    /// The given offsets from the seat.
+    Custom(Vec<(i32, i32)>),
+}
+
+impl Neighbourhood {
+    pub const DIRECTIONS: [(i32, i32); 8] = [
+        (0, -1),  // up
+        (1, -1),  // up right
+        (1, 0),   // right
+        (1, 1),   // right down
+        (0, 1),   // down
+        (-1, 1),  // down left
+        (-1, 0),  // left
+        (-1, -1), // up left
+    ];
+
+    /// A radius below 1 would leave every seat without neighbours.
+    pub fn radius(radius: i32) -> Result<Neighbourhood, String> {
+        if radius < 1 {
+            Err(format!("Radius Has To Be At Least 1, Got {}", radius))
+        } else {
+            Ok(Neighbourhood::Radius(radius))
+        }
+    }
+
+    /// The fixed offsets to look at; line of sight has none since it depends on the layout.
+    /// A radius is cut down to `max_distance`, past which no seat can be, and a seat is never
+    /// its own neighbour.
+    pub fn get_offsets(&self, max_distance: i32) -> Vec<(i32, i32)> {
+        match self {
+            Neighbourhood::Adjacent => Self::DIRECTIONS.to_vec(),
+            Neighbourhood::LineOfSight => Vec::new(),
+            Neighbourhood::Radius(radius) => {
+                let radius = (*radius).min(max_distance);
+
+                (-radius..=radius)
+                    .flat_map(|y| (-radius..=radius).map(move |x| (x, y)))
+                    .filter(|&offset| offset != (0, 0))
+                    .collect()
+            }
+            Neighbourhood::Custom(offsets) => offsets
+                .iter()
+                .copied()
+                .filter(|&offset| offset != (0, 0))
+                .collect(),
+        }
+    }
+}
+
+/// How seats change from one round to the next.
+///
+/// An empty seat is taken if at most `occupy_threshold` of its neighbours are occupied, and an
+/// occupied seat is left if at least `vacate_threshold` of them are.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SeatingRules {
+    pub neighbourhood: Neighbourhood,
+    pub occupy_threshold: usize,
+    pub vacate_threshold: usize,
+}
+
+impl SeatingRules {
+    pub fn new(neighbourhood: Neighbourhood, vacate_threshold: usize) -> SeatingRules {
+        SeatingRules {
+            neighbourhood,
+            occupy_threshold: 0,
+            vacate_threshold,
+        }
+    }
+
+    /// The part 1 rules.
+    pub fn adjacent() -> SeatingRules {
+        SeatingRules::new(Neighbourhood::Adjacent, 4)
+    }
+
+    /// The part 2 rules.
+    pub fn line_of_sight() -> SeatingRules {
+        SeatingRules::new(Neighbourhood::LineOfSight, 5)
+    }
+
+    pub fn with_occupy_threshold(mut self, occupy_threshold: usize) -> SeatingRules {
+        self.occupy_threshold = occupy_threshold;
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbourhood_get_offsets() {
+        let result_1 = Neighbourhood::Radius(1).get_offsets(10);
+        let result_2 = Neighbourhood::Radius(2).get_offsets(10).len();
+        let result_3 = Neighbourhood::LineOfSight.get_offsets(10);
+        let result_4 = Neighbourhood::Radius(100_000).get_offsets(2).len();
+        let result_5 = Neighbourhood::Custom(vec![(0, 0), (2, 1)]).get_offsets(10);
+
+        let expected_1 = vec![
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ];
+
+        assert_eq!(result_1, expected_1);
+        assert_eq!(result_2, 24);
+        assert!(result_3.is_empty());
+        assert_eq!(result_4, 24);
+        assert_eq!(result_5, vec![(2, 1)]);
+    }
+
+    #[test]
+    fn test_neighbourhood_radius() {
+        assert_eq!(Neighbourhood::radius(3), Ok(Neighbourhood::Radius(3)));
+        assert!(Neighbourhood::radius(0).is_err());
+        assert!(Neighbourhood::radius(-2).is_err());
+    }
+}
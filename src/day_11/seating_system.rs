@@ -1,230 +1,484 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+use super::rules::{Neighbourhood, SeatingRules};
+
+/// A blocked seat can never be taken; an aisle stays empty like the floor but, like a seat,
+/// blocks the line of sight.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 enum LayoutObject {
     Floor,
     OccupiedSeat,
     EmptySeat,
+    BlockedSeat,
+    Aisle,
 }
 
 impl LayoutObject {
     fn new(info: char) -> LayoutObject {
+        LayoutObject::parse(info).unwrap_or_else(|| panic!("No Object For: {}", info))
+    }
+
+    fn parse(info: char) -> Option<LayoutObject> {
         match info {
-            '.' => LayoutObject::Floor,
-            'L' => LayoutObject::EmptySeat,
-            '#' => LayoutObject::OccupiedSeat,
-            _ => panic!("No Object For: {}", info),
+            '.' => Some(LayoutObject::Floor),
+            'L' => Some(LayoutObject::EmptySeat),
+            '#' => Some(LayoutObject::OccupiedSeat),
+            'X' => Some(LayoutObject::BlockedSeat),
+            '|' => Some(LayoutObject::Aisle),
+            _ => None,
         }
     }
 
     fn switch_occupancy(&self) -> LayoutObject {
         match self {
-            LayoutObject::Floor => LayoutObject::Floor,
             LayoutObject::EmptySeat => LayoutObject::OccupiedSeat,
             LayoutObject::OccupiedSeat => LayoutObject::EmptySeat,
+            other => *other,
+        }
+    }
+
+    fn is_seat(&self) -> bool {
+        matches!(self, LayoutObject::EmptySeat | LayoutObject::OccupiedSeat)
+    }
+
+    fn blocks_sight(&self) -> bool {
+        *self != LayoutObject::Floor
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            LayoutObject::Floor => '.',
+            LayoutObject::EmptySeat => 'L',
+            LayoutObject::OccupiedSeat => '#',
+            LayoutObject::BlockedSeat => 'X',
+            LayoutObject::Aisle => '|',
         }
     }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum SimulationOutcome {
+    Stable {
+        generation: usize,
+    },
+    /// Generation `first_generation` comes back every `period` generations.
+    Oscillating {
+        first_generation: usize,
+        period: usize,
+    },
+    StepLimitReached {
+        step_limit: usize,
+    },
+}
+
+impl fmt::Display for SimulationOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimulationOutcome::Stable { generation } => {
+                write!(f, "Stable from generation {}", generation)
+            }
+            SimulationOutcome::Oscillating {
+                first_generation,
+                period,
+            } => write!(
+                f,
+                "Oscillates with period {} from generation {}",
+                period, first_generation
+            ),
+            SimulationOutcome::StepLimitReached { step_limit } => {
+                write!(f, "Step limit of {} generations reached", step_limit)
+            }
+        }
+    }
+}
+
+/// The layout is stored row by row in a flat vector. The neighbours of every seat are worked
+/// out once per neighbourhood, and each round is written into a second buffer that is swapped
+/// in afterwards.
 #[derive(Debug, PartialEq)]
 pub struct SeatingSystem {
-    layout: HashMap<(i32, i32), LayoutObject>,
+    layout: Vec<LayoutObject>,
+    next_layout: Vec<LayoutObject>,
     size: (usize, usize),
+    neighbourhood: Option<Neighbourhood>,
+    neighbours: Vec<Vec<usize>>,
+    rules: Option<SeatingRules>,
     current_cycle: u32,
     is_stable: bool,
     original_input: Vec<String>,
+    record_history: bool,
+    history: Vec<String>,
+    generations_by_hash: HashMap<u64, usize>,
+    repeated_generation: Option<usize>,
 }
 
 impl SeatingSystem {
-    const SURROUNDING_LOCATIONS: [(i32, i32); 8] = [
-        (0, -1),  // up
-        (1, -1),  // up right
-        (1, 0),   // right
-        (1, 1),   // right down
-        (0, 1),   // down
-        (-1, 1),  // down left
-        (-1, 0),  // left
-        (-1, -1), // up left
-    ];
-
     pub fn new(info: &[String]) -> SeatingSystem {
-        let mut temp = HashMap::new();
-
-        let size = (info.get(0).unwrap_or(&String::new()).len(), info.len());
-
-        for (row_index, row) in info.iter().enumerate() {
-            for (col_index, col) in row.chars().enumerate() {
-                let point = (col_index as i32, row_index as i32);
-
-                let layout_object = LayoutObject::new(col);
-
-                temp.insert(point, layout_object);
-            }
+        let width = info
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut layout = Vec::with_capacity(width * info.len());
+
+        // Short rows are padded with floor, which nobody can sit on or sees past.
+        for row in info {
+            layout.extend(row.chars().map(LayoutObject::new));
+            layout.resize(
+                layout.len() + width - row.chars().count(),
+                LayoutObject::Floor,
+            );
         }
 
-        SeatingSystem {
-            layout: temp,
-            size: size,
+        let mut seating_system = SeatingSystem {
+            next_layout: layout.clone(),
+            layout,
+            size: (width, info.len()),
+            neighbourhood: None,
+            neighbours: Vec::new(),
+            rules: None,
             current_cycle: 0,
             is_stable: false,
             original_input: info.iter().map(|s| s.to_string()).collect(),
-        }
+            record_history: false,
+            history: Vec::new(),
+            generations_by_hash: HashMap::new(),
+            repeated_generation: None,
+        };
+
+        seating_system.record_generation();
+
+        seating_system
+    }
+
+    /// Keeps every generation from now on as text, for `get_history`.
+    pub fn with_history(mut self) -> SeatingSystem {
+        self.record_history = true;
+        self.history = vec![self.to_string()];
+
+        self
     }
 
     pub fn number_of_occupied_seats(&self) -> usize {
         self.layout
-            .values()
+            .iter()
             .filter(|object| **object == LayoutObject::OccupiedSeat)
             .count()
     }
 
     pub fn simulate_until_stable_with_los(&mut self) {
-        while !self.is_stable {
-            self.simulate_cycle(5, true);
-        }
+        self.simulate_until_stable_with_rules(&SeatingRules::line_of_sight());
     }
 
     pub fn simulate_until_stable(&mut self) {
-        while !self.is_stable {
-            self.simulate_cycle(4, false);
+        self.simulate_until_stable_with_rules(&SeatingRules::adjacent());
+    }
+
+    pub fn simulate_until_stable_with_rules(&mut self, rules: &SeatingRules) -> SimulationOutcome {
+        self.simulate(rules, None)
+    }
+
+    /// Simulates until the layout stops changing, returns to an earlier generation or
+    /// `step_limit` generations have passed. Earlier generations only count if they were
+    /// simulated with the same rules.
+    pub fn simulate(
+        &mut self,
+        rules: &SeatingRules,
+        step_limit: Option<usize>,
+    ) -> SimulationOutcome {
+        if self.rules.as_ref() != Some(rules) {
+            self.restart_history();
+            self.rules = Some(rules.clone());
+        }
+
+        loop {
+            let generation = self.current_cycle as usize;
+
+            if self.is_stable {
+                return SimulationOutcome::Stable { generation };
+            }
+
+            if let Some(first_generation) = self.repeated_generation {
+                return SimulationOutcome::Oscillating {
+                    first_generation,
+                    period: generation - first_generation,
+                };
+            }
+
+            if let Some(step_limit) = step_limit.filter(|&step_limit| step_limit <= generation) {
+                return SimulationOutcome::StepLimitReached { step_limit };
+            }
+
+            self.simulate_cycle(rules);
         }
     }
 
+    /// Every generation since `with_history` in the `L`/`#`/`.` format, starting over when the
+    /// rules change or a snapshot is loaded.
+    pub fn get_history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Shows the layout of `other` with `+` for seats that became occupied, `-` for seats that
+    /// were left and `?` for anything else that differs.
+    pub fn diff(&self, other: &SeatingSystem) -> Result<String, String> {
+        if self.size != other.size {
+            return Err(format!(
+                "Layouts Differ In Size: {:?} And {:?}",
+                self.size, other.size
+            ));
+        }
+
+        let width = self.size.0;
+
+        let rows: Vec<String> = (0..self.size.1)
+            .map(|row| {
+                (row * width..(row + 1) * width)
+                    .map(|index| match (self.layout[index], other.layout[index]) {
+                        (old, new) if old == new => new.to_char(),
+                        (LayoutObject::EmptySeat, LayoutObject::OccupiedSeat) => '+',
+                        (LayoutObject::OccupiedSeat, LayoutObject::EmptySeat) => '-',
+                        _ => '?',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(rows.join("\n"))
+    }
+
+    /// Replaces the current layout, for example with a generation from the text frame export.
+    /// A leading `generation N` line sets the current cycle. The history starts over from the
+    /// snapshot.
+    pub fn load_snapshot(&mut self, info: &[String]) -> Result<(), String> {
+        let (generation, rows) = match info
+            .first()
+            .and_then(|line| line.strip_prefix("generation "))
+        {
+            Some(generation) => (
+                generation
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|error| error.to_string())?,
+                &info[1..],
+            ),
+            None => (self.current_cycle, info),
+        };
+
+        let rows: Vec<&String> = rows.iter().filter(|row| !row.is_empty()).collect();
+
+        if rows.len() != self.size.1 {
+            return Err(format!(
+                "Snapshot Has {} Rows Instead Of {}",
+                rows.len(),
+                self.size.1
+            ));
+        }
+
+        let mut layout = Vec::with_capacity(self.layout.len());
+
+        for row in rows {
+            if row.chars().count() != self.size.0 {
+                return Err(format!("Row Is Not {} Objects Wide: {}", self.size.0, row));
+            }
+
+            for info in row.chars() {
+                layout.push(LayoutObject::parse(info).ok_or(format!("No Object For: {}", info))?);
+            }
+        }
+
+        let same_seats = layout
+            .iter()
+            .zip(&self.layout)
+            .all(|(new, old)| new == old || new.switch_occupancy() == *old);
+
+        if !same_seats {
+            self.neighbourhood = None;
+        }
+
+        self.next_layout = layout.clone();
+        self.layout = layout;
+        self.current_cycle = generation;
+
+        self.restart_history();
+
+        Ok(())
+    }
+
     pub fn reset(&mut self) {
-        *self = SeatingSystem::new(&self.original_input);
+        let seating_system = SeatingSystem::new(&self.original_input);
+
+        *self = if self.record_history {
+            seating_system.with_history()
+        } else {
+            seating_system
+        };
     }
 
-    fn simulate_cycle(&mut self, occupancy_threshold: usize, los: bool) {
-        let old_layout = self.layout.clone();
+    fn simulate_cycle(&mut self, rules: &SeatingRules) {
+        self.update_neighbours(&rules.neighbourhood);
+
+        let layout = &self.layout;
+        let next_layout = &mut self.next_layout;
 
         let mut seats_changed = 0;
 
-        for row in 0..self.size.1 {
-            for col in 0..self.size.0 {
-                let current_location = (col as i32, row as i32);
+        for (index, (object, neighbours)) in layout.iter().zip(&self.neighbours).enumerate() {
+            let occupied_seats = neighbours
+                .iter()
+                .filter(|&&neighbour| layout[neighbour] == LayoutObject::OccupiedSeat)
+                .count();
 
-                let seat_changed = self.simulate_seat_change(
-                    current_location,
-                    &old_layout,
-                    occupancy_threshold,
-                    los,
-                );
+            let seat_changes = match object {
+                LayoutObject::EmptySeat => occupied_seats <= rules.occupy_threshold,
+                LayoutObject::OccupiedSeat => rules.vacate_threshold <= occupied_seats,
+                _ => false,
+            };
 
-                if seat_changed {
-                    seats_changed += 1;
-                }
-            }
+            next_layout[index] = if seat_changes {
+                seats_changed += 1;
+
+                object.switch_occupancy()
+            } else {
+                *object
+            };
         }
 
         if seats_changed == 0 {
             self.is_stable = true;
         } else {
+            std::mem::swap(&mut self.layout, &mut self.next_layout);
+
             self.current_cycle += 1;
+
+            self.record_generation();
         }
     }
 
-    fn simulate_seat_change(
-        &mut self,
-        location: (i32, i32),
-        old_layout: &HashMap<(i32, i32), LayoutObject>,
-        occupancy_threshold: usize,
-        los: bool,
-    ) -> bool {
-        let temp = format!("Location Err: {:?}\nSize: {:?}", location, self.size);
-        let object = old_layout.get(&location).expect(&temp).clone();
-
-        let surrounding_seats = if los {
-            self.get_objects_in_los(location, old_layout)
-        } else {
-            self.get_surrounding_seats(location, &old_layout)
-        };
+    /// Works out which seats every seat looks at. Only seats can change and only seats can be
+    /// occupied, so everything else is left out of the lists.
+    fn update_neighbours(&mut self, neighbourhood: &Neighbourhood) {
+        if self.neighbourhood.as_ref() == Some(neighbourhood) {
+            return;
+        }
 
-        let surrounding_is_empty = surrounding_seats
-            .iter()
-            .all(|&o| (o == LayoutObject::Floor || o == LayoutObject::EmptySeat));
+        let offsets = neighbourhood.get_offsets(self.size.0.max(self.size.1) as i32);
+
+        let neighbours = (0..self.layout.len())
+            .map(|index| {
+                if !self.layout[index].is_seat() {
+                    Vec::new()
+                } else if *neighbourhood == Neighbourhood::LineOfSight {
+                    self.get_seats_in_los(index)
+                } else {
+                    self.get_indices_at_offsets(index, &offsets)
+                        .into_iter()
+                        .filter(|&neighbour| self.layout[neighbour].is_seat())
+                        .collect()
+                }
+            })
+            .collect();
 
-        let surrounding_above_threshold = occupancy_threshold
-            <= surrounding_seats
-                .iter()
-                .filter(|o| **o == LayoutObject::OccupiedSeat)
-                .count();
+        self.neighbours = neighbours;
+        self.neighbourhood = Some(neighbourhood.clone());
+    }
+
+    /// Remembers a hash of the current layout and notes when it already occurred before.
+    fn record_generation(&mut self) {
+        let mut hasher = DefaultHasher::new();
+
+        self.layout.hash(&mut hasher);
 
-        self.change_seats(
-            location,
-            object,
-            surrounding_is_empty,
-            surrounding_above_threshold,
-        )
+        let hash = hasher.finish();
+
+        if self.repeated_generation.is_none() {
+            self.repeated_generation = self.generations_by_hash.get(&hash).copied();
+        }
+
+        self.generations_by_hash
+            .entry(hash)
+            .or_insert(self.current_cycle as usize);
+
+        if self.record_history {
+            self.history.push(self.to_string());
+        }
     }
 
-    fn get_surround_locations(&self, location: &(i32, i32)) -> Vec<(i32, i32)> {
-        Self::SURROUNDING_LOCATIONS
-            .iter()
-            .map(|p| (p.0 + location.0, p.1 + location.1))
-            .collect()
+    /// Forgets the earlier generations, which no longer say anything about the current
+    /// layout or rules.
+    fn restart_history(&mut self) {
+        self.is_stable = false;
+        self.history.clear();
+        self.generations_by_hash.clear();
+        self.repeated_generation = None;
+
+        self.record_generation();
+    }
+
+    fn get_index(&self, location: (i32, i32)) -> Option<usize> {
+        let (width, height) = (self.size.0 as i32, self.size.1 as i32);
+
+        if (0..width).contains(&location.0) && (0..height).contains(&location.1) {
+            Some((location.1 * width + location.0) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn get_location(&self, index: usize) -> (i32, i32) {
+        ((index % self.size.0) as i32, (index / self.size.0) as i32)
     }
 
-    fn get_surrounding_seats(
-        &self,
-        location: (i32, i32),
-        layout: &HashMap<(i32, i32), LayoutObject>,
-    ) -> Vec<LayoutObject> {
-        self.get_surround_locations(&location)
+    fn get_indices_at_offsets(&self, index: usize, offsets: &[(i32, i32)]) -> Vec<usize> {
+        let location = self.get_location(index);
+
+        offsets
             .iter()
-            .filter(|p| layout.contains_key(p))
-            .map(|p| layout.get(p).unwrap().clone())
+            .filter_map(|p| self.get_index((p.0 + location.0, p.1 + location.1)))
             .collect()
     }
 
-    fn get_objects_in_los(
-        &self,
-        location: (i32, i32),
-        layout: &HashMap<(i32, i32), LayoutObject>,
-    ) -> Vec<LayoutObject> {
-        Self::SURROUNDING_LOCATIONS
+    fn get_seats_in_los(&self, index: usize) -> Vec<usize> {
+        Neighbourhood::DIRECTIONS
             .iter()
-            .map(|&scale| self.get_first_seat_location_in_line(location, scale, layout))
-            .filter(|item| item.is_some())
-            .map(|item| layout.get(&item.unwrap()).unwrap().clone())
+            .filter_map(|&scale| self.get_first_seat_location_in_line(index, scale))
+            .filter(|&seat| self.layout[seat].is_seat())
             .collect()
     }
 
-    fn get_first_seat_location_in_line(
-        &self,
-        location: (i32, i32),
-        scale: (i32, i32),
-        layout: &HashMap<(i32, i32), LayoutObject>,
-    ) -> Option<(i32, i32)> {
-        let next = (location.0 + scale.0, location.1 + scale.1);
+    /// Nothing ever turns into floor, so what blocks the view never changes.
+    fn get_first_seat_location_in_line(&self, index: usize, scale: (i32, i32)) -> Option<usize> {
+        let mut location = self.get_location(index);
 
-        if let Some(object) = layout.get(&next) {
-            match object {
-                LayoutObject::EmptySeat | LayoutObject::OccupiedSeat => Some(next),
-                LayoutObject::Floor => self.get_first_seat_location_in_line(next, scale, layout),
+        loop {
+            location = (location.0 + scale.0, location.1 + scale.1);
+
+            let next = self.get_index(location)?;
+
+            if self.layout[next].blocks_sight() {
+                return Some(next);
             }
-        } else {
-            None
         }
     }
+}
 
-    fn change_seats(
-        &mut self,
-        location: (i32, i32),
-        object: LayoutObject,
-        surrounding_is_empty: bool,
-        surrounding_above_threshold: bool,
-    ) -> bool {
-        if (object == LayoutObject::EmptySeat) && surrounding_is_empty {
-            *self.layout.get_mut(&location).unwrap() = object.switch_occupancy();
-
-            true
-        } else if (object == LayoutObject::OccupiedSeat) && surrounding_above_threshold {
-            *self.layout.get_mut(&location).unwrap() = object.switch_occupancy();
-
-            true
-        } else {
-            false
+impl fmt::Display for SeatingSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (row, objects) in self.layout.chunks(self.size.0.max(1)).enumerate() {
+            if row != 0 {
+                writeln!(f)?;
+            }
+
+            for object in objects {
+                write!(f, "{}", object.to_char())?;
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -321,6 +575,20 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_seating_system_new_ragged_rows() {
+        let input = vec![String::from("L.L"), String::from("#"), String::from("")];
+
+        let seating_system = SeatingSystem::new(&input);
+
+        let result = seating_system.to_string();
+
+        let expected = "L.L\n#..\n...";
+
+        assert_eq!(seating_system.size, (3, 3));
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn test_seating_system_number_of_occupied_seats() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
@@ -329,7 +597,7 @@ mod tests {
 
         let result_1 = seating_system.number_of_occupied_seats();
 
-        seating_system.simulate_cycle(4, false);
+        seating_system.simulate_cycle(&SeatingRules::adjacent());
 
         let result_2 = seating_system.number_of_occupied_seats();
 
@@ -357,45 +625,34 @@ mod tests {
     #[test]
     fn test_seating_system_simulate_cycle() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
-        let input_2: Vec<String> = TEST_DATA_THIRD_CYCLE
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
 
-        let mut seating_system = SeatingSystem::new(&input);
+        let mut seating_system = SeatingSystem::new(&input).with_history();
 
-        seating_system.simulate_cycle(4, false);
-        seating_system.simulate_cycle(4, false);
-        seating_system.simulate_cycle(4, false);
+        seating_system.simulate_cycle(&SeatingRules::adjacent());
+        seating_system.simulate_cycle(&SeatingRules::adjacent());
+        seating_system.simulate_cycle(&SeatingRules::adjacent());
 
-        let expected_system = SeatingSystem {
-            current_cycle: 3,
-            is_stable: false,
-            original_input: input,
-            ..SeatingSystem::new(&input_2)
-        };
+        let expected_layout = TEST_DATA_THIRD_CYCLE.join("\n");
 
-        assert_eq!(seating_system, expected_system);
+        assert_eq!(seating_system.to_string(), expected_layout);
+        assert_eq!(seating_system.current_cycle, 3);
+        assert!(!seating_system.is_stable);
+        assert_eq!(seating_system.get_history().len(), 4);
+        assert_eq!(
+            seating_system.get_history()[3],
+            TEST_DATA_THIRD_CYCLE.join("\n")
+        );
     }
 
     #[test]
-    fn test_seating_system_get_surrounding_locations() {
+    fn test_seating_system_get_indices_at_offsets() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
 
         let seating_system = SeatingSystem::new(&input);
 
-        let result = seating_system.get_surround_locations(&(9, 9));
+        let result = seating_system.get_indices_at_offsets(99, &Neighbourhood::DIRECTIONS);
 
-        let expected = vec![
-            (9, 8),
-            (10, 8),
-            (10, 9),
-            (10, 10),
-            (9, 10),
-            (8, 10),
-            (8, 9),
-            (8, 8),
-        ];
+        let expected = vec![89, 98, 88];
 
         assert_eq!(result, expected);
     }
@@ -406,11 +663,7 @@ mod tests {
 
         let seating_system = SeatingSystem::new(&input);
 
-        let old_layout = seating_system.layout.clone();
-
-        let location = (3, 3);
-
-        let result = seating_system.get_objects_in_los(location, &old_layout);
+        let result = seating_system.get_seats_in_los(24);
 
         assert!(result.is_empty());
     }
@@ -421,18 +674,36 @@ mod tests {
 
         let seating_system = SeatingSystem::new(&input);
 
-        let old_layout = seating_system.layout.clone();
-
-        let location = (3, 4);
+        let location = 39; // (3, 4)
         let scale = (1, -1); // up right;
 
-        let result = seating_system.get_first_seat_location_in_line(location, scale, &old_layout);
+        let result = seating_system.get_first_seat_location_in_line(location, scale);
 
-        let expected = Some((7, 0));
+        let expected = Some(7);
 
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_seating_system_update_neighbours() {
+        let input: Vec<String> = TEST_DATA_LOS.iter().map(|s| s.to_string()).collect();
+
+        let mut seating_system = SeatingSystem::new(&input);
+
+        seating_system.update_neighbours(&Neighbourhood::LineOfSight);
+
+        let result_los = seating_system.neighbours[39].len();
+
+        seating_system.update_neighbours(&Neighbourhood::Adjacent);
+
+        let result_adjacent = seating_system.neighbours[39].clone();
+        let result_floor = seating_system.neighbours[0].clone();
+
+        assert_eq!(result_los, 8);
+        assert_eq!(result_adjacent, vec![49, 38]);
+        assert!(result_floor.is_empty());
+    }
+
     #[test]
     fn test_seating_system_number_of_cycles_until_stable_los() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
@@ -446,4 +717,136 @@ mod tests {
         assert_eq!(seating_system.current_cycle, expected);
         assert!(seating_system.is_stable);
     }
+
+    #[test]
+    fn test_seating_system_simulate_until_stable_with_rules() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut seating_system = SeatingSystem::new(&input);
+
+        seating_system.simulate_until_stable_with_rules(&SeatingRules::new(
+            Neighbourhood::Custom(Neighbourhood::DIRECTIONS.to_vec()),
+            4,
+        ));
+
+        let result_custom = seating_system.number_of_occupied_seats();
+
+        seating_system.reset();
+        seating_system.simulate_until_stable_with_rules(&SeatingRules::adjacent());
+
+        let result_adjacent = seating_system.number_of_occupied_seats();
+
+        seating_system.reset();
+        seating_system
+            .simulate_until_stable_with_rules(&SeatingRules::new(Neighbourhood::Radius(2), 25));
+
+        let result_radius = seating_system.number_of_occupied_seats();
+
+        assert_eq!(result_custom, 37);
+        assert_eq!(result_adjacent, 37);
+        assert_eq!(result_radius, 71);
+    }
+
+    #[test]
+    fn test_seating_system_blocked_seats_and_aisles() {
+        let input: Vec<String> = vec!["LXL", "L|L", "..."]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let mut seating_system = SeatingSystem::new(&input);
+
+        seating_system.simulate_cycle(&SeatingRules::adjacent());
+
+        let result_blocked = seating_system.layout[1];
+        let result_aisle = seating_system.layout[4];
+
+        assert_eq!(result_blocked, LayoutObject::BlockedSeat);
+        assert_eq!(result_aisle, LayoutObject::Aisle);
+        assert_eq!(seating_system.number_of_occupied_seats(), 4);
+
+        let result_los = seating_system.get_first_seat_location_in_line(3, (1, 0));
+
+        assert_eq!(result_los, Some(4));
+        assert_eq!(seating_system.get_seats_in_los(3), vec![0]);
+    }
+
+    #[test]
+    fn test_seating_system_simulate_outcomes() {
+        let input = vec![String::from("LL")];
+        let rules = SeatingRules::new(Neighbourhood::Adjacent, 1).with_occupy_threshold(1);
+
+        let mut seating_system = SeatingSystem::new(&input).with_history();
+
+        let result_1 = seating_system.simulate(&rules, Some(1));
+        let result_2 = seating_system.simulate(&rules, None);
+
+        let expected_1 = SimulationOutcome::StepLimitReached { step_limit: 1 };
+        let expected_2 = SimulationOutcome::Oscillating {
+            first_generation: 0,
+            period: 2,
+        };
+
+        assert_eq!(result_1, expected_1);
+        assert_eq!(result_2, expected_2);
+        assert_eq!(seating_system.get_history(), &["LL", "##", "LL"]);
+
+        let result_3 = seating_system.simulate(&SeatingRules::adjacent(), None);
+
+        assert_eq!(result_3, SimulationOutcome::Stable { generation: 3 });
+        assert_eq!(seating_system.get_history(), &["LL", "##"]);
+
+        seating_system.reset();
+
+        let result_4 = seating_system.simulate(&SeatingRules::adjacent(), Some(1));
+
+        assert_eq!(
+            result_4,
+            SimulationOutcome::StepLimitReached { step_limit: 1 }
+        );
+        assert_eq!(
+            seating_system.simulate(&SeatingRules::adjacent(), Some(10)),
+            SimulationOutcome::Stable { generation: 1 }
+        );
+        assert_eq!(seating_system.get_history().len(), 2);
+    }
+
+    #[test]
+    fn test_seating_system_diff() {
+        let before: Vec<String> = vec!["L.#", "#LL"].into_iter().map(String::from).collect();
+        let after: Vec<String> = vec!["#.#", "LLX"].into_iter().map(String::from).collect();
+
+        let result = SeatingSystem::new(&before).diff(&SeatingSystem::new(&after));
+        let result_size = SeatingSystem::new(&before).diff(&SeatingSystem::new(&after[..1]));
+
+        let expected = Ok(String::from("+.#\n-L?"));
+
+        assert_eq!(result, expected);
+        assert!(result_size.is_err());
+    }
+
+    #[test]
+    fn test_seating_system_load_snapshot() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut seating_system = SeatingSystem::new(&input).with_history();
+
+        let mut snapshot = vec![String::from("generation 3")];
+
+        snapshot.extend(TEST_DATA_THIRD_CYCLE.iter().map(|s| s.to_string()));
+
+        seating_system.load_snapshot(&snapshot).unwrap();
+
+        let result_1 = seating_system.to_string();
+        let result_2 = seating_system.simulate_until_stable_with_rules(&SeatingRules::adjacent());
+
+        assert_eq!(result_1, TEST_DATA_THIRD_CYCLE.join("\n"));
+        assert_eq!(result_2, SimulationOutcome::Stable { generation: 5 });
+        assert_eq!(seating_system.number_of_occupied_seats(), 37);
+        assert_eq!(seating_system.get_history().len(), 3);
+        assert!(seating_system.load_snapshot(&input[..9]).is_err());
+        assert!(seating_system
+            .load_snapshot(&[String::from("generation x")])
+            .is_err());
+    }
 }
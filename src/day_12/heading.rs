@@ -0,0 +1,142 @@
+use std::fmt;
+
+use crate::util::point_2d::Point2d;
+
+/// Degrees counter-clockwise from east, kept between 0 and 360.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Heading {
+    degrees: i32,
+}
+
+impl Heading {
+    pub const EAST: Heading = Heading { degrees: 0 };
+
+    const NAMES: [&'static str; 8] = ["E", "NE", "N", "NW", "W", "SW", "S", "SE"];
+
+    pub fn new(degrees: i32) -> Heading {
+        Heading {
+            degrees: degrees.rem_euclid(360),
+        }
+    }
+
+    pub fn get_degrees(&self) -> i32 {
+        self.degrees
+    }
+
+    pub fn rotate(&self, degrees: i32) -> Heading {
+        Heading::new(self.degrees + degrees.rem_euclid(360))
+    }
+
+    /// The move one unit forward makes; on a diagonal that is one unit along both axes.
+    pub fn get_step(&self) -> Option<Point2d<i32>> {
+        match self.degrees {
+            0 => Some(Point2d::new(1, 0)),
+            45 => Some(Point2d::new(1, 1)),
+            90 => Some(Point2d::new(0, 1)),
+            135 => Some(Point2d::new(-1, 1)),
+            180 => Some(Point2d::new(-1, 0)),
+            225 => Some(Point2d::new(-1, -1)),
+            270 => Some(Point2d::new(0, -1)),
+            315 => Some(Point2d::new(1, -1)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Heading {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.degrees % 45 == 0 {
+            write!(f, "{}", Heading::NAMES[(self.degrees / 45) as usize])
+        } else {
+            write!(f, "{} degrees", self.degrees)
+        }
+    }
+}
+
+/// Which headings the ship can steer to.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Compass {
+    FourWay,
+    EightWay,
+}
+
+impl Compass {
+    pub fn get_step(&self) -> i32 {
+        match self {
+            Compass::FourWay => 90,
+            Compass::EightWay => 45,
+        }
+    }
+
+    pub fn check_rotation(&self, degrees: i32) -> Result<(), String> {
+        if degrees % self.get_step() == 0 {
+            Ok(())
+        } else {
+            Err(format!(
+                "Cannot Rotate By {} Degrees, Only By Multiples Of {}",
+                degrees,
+                self.get_step()
+            ))
+        }
+    }
+}
+
+/// Turns `point` counter-clockwise around the origin; only quarter turns keep it on the grid.
+pub fn rotate_point(point: Point2d<i32>, degrees: i32) -> Result<Point2d<i32>, String> {
+    if degrees % 90 != 0 {
+        return Err(format!("Cannot Rotate A Point By {} Degrees", degrees));
+    }
+
+    let quarter_turns = degrees.rem_euclid(360) / 90;
+
+    Ok((0..quarter_turns).fold(point, |point, _| Point2d::new(-point.y, point.x)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_rotate() {
+        let result_1 = Heading::EAST.rotate(90);
+        let result_2 = Heading::EAST.rotate(-90);
+        let result_3 = Heading::EAST.rotate(450 + 45);
+        let result_4 = Heading::new(-720);
+
+        assert_eq!(result_1, Heading::new(90));
+        assert_eq!(result_2, Heading::new(270));
+        assert_eq!(result_3.to_string(), "NW");
+        assert_eq!(result_4, Heading::EAST);
+        assert_eq!(Heading::new(30).to_string(), "30 degrees");
+    }
+
+    #[test]
+    fn test_heading_get_step() {
+        let result_1 = Heading::new(225).get_step();
+        let result_2 = Heading::new(30).get_step();
+
+        assert_eq!(result_1, Some(Point2d::new(-1, -1)));
+        assert_eq!(result_2, None);
+    }
+
+    #[test]
+    fn test_compass_check_rotation() {
+        assert!(Compass::FourWay.check_rotation(-270).is_ok());
+        assert!(Compass::FourWay.check_rotation(45).is_err());
+        assert!(Compass::EightWay.check_rotation(135).is_ok());
+        assert!(Compass::EightWay.check_rotation(30).is_err());
+    }
+
+    #[test]
+    fn test_rotate_point() {
+        let point = Point2d::new(10, 4);
+
+        let result_1 = rotate_point(point, 90);
+        let result_2 = rotate_point(point, -90);
+        let result_3 = rotate_point(point, 45);
+
+        assert_eq!(result_1, Ok(Point2d::new(-4, 10)));
+        assert_eq!(result_2, Ok(Point2d::new(4, -10)));
+        assert!(result_3.is_err());
+    }
+}
@@ -1,6 +1,19 @@
+mod heading;
+mod navigation;
+mod route;
+mod script;
 mod ship;
 
+use std::fs;
+
 use crate::util::file_reader::to_string_vector;
+use crate::{get_user_input, wants_extras};
+
+use heading::Compass;
+use navigation::{
+    DirectModel, NavigationModel, RelativeWaypointModel, VelocityModel, WaypointModel,
+};
+use route::Route;
 use ship::{Action, Ship};
 
 pub fn run_day_12() {
@@ -12,19 +25,154 @@ pub fn run_day_12() {
 
             let mut ship = Ship::new();
 
-            ship.navigate(&actions, false);
+            if let Err(error) = ship.navigate(&actions, &DirectModel::new(Compass::FourWay)) {
+                println!("Error Navigating: {}", error);
+            }
 
             let part_1 = ship.manhattan_distance_moved();
+            let part_1_route = ship.get_route().clone();
 
             ship.reset();
 
-            ship.navigate(&actions, true);
+            if let Err(error) = ship.navigate(&actions, &WaypointModel) {
+                println!("Error Navigating: {}", error);
+            }
 
             let part_2 = ship.manhattan_distance_moved();
 
             println!("Day 12 Part 1: {}", part_1);
             println!("Day 12 Part 2: {}", part_2);
+
+            print_route_summary("Part 1", &part_1_route);
+            print_route_summary("Part 2", ship.get_route());
+
+            if !wants_extras() {
+                return;
+            }
+
+            navigate_with_compass(&actions);
+            export_route(ship.get_route());
+            undo_actions(&mut ship, &actions);
+            run_script();
         }
         Err(error) => println!("Error Parsing File: {:?}", error),
     };
 }
+
+fn print_route_summary(name: &str, route: &Route) {
+    if let (Some((farthest_point, distance)), Some((lower, upper))) =
+        (route.farthest_point(), route.bounding_box())
+    {
+        println!(
+            "{} Route: {} travelled, farthest ({}, {}) at {}, bounds ({}, {}) to ({}, {})",
+            name,
+            route.total_distance(),
+            farthest_point.x,
+            farthest_point.y,
+            distance,
+            lower.x,
+            lower.y,
+            upper.x,
+            upper.y
+        );
+    }
+}
+
+fn navigate_with_compass(actions: &[Action]) {
+    print!("Compass for part 1 (0 = skip, 1 = four-way, 2 = eight-way): ");
+
+    let compass = match get_user_input() {
+        1 => Compass::FourWay,
+        2 => Compass::EightWay,
+        _ => return,
+    };
+
+    let mut ship = Ship::new();
+
+    match ship.navigate(actions, &DirectModel::new(compass)) {
+        Ok(()) => println!(
+            "Part 1 with the {:?} compass: {}",
+            compass,
+            ship.manhattan_distance_moved()
+        ),
+        Err(error) => println!("Error Navigating: {}", error),
+    }
+}
+
+fn export_route(route: &Route) {
+    print!("Export the part 2 route (0 = no, 1 = csv, 2 = svg): ");
+
+    let (path, contents) = match get_user_input() {
+        1 => ("output/day_12_route.csv", route.to_csv()),
+        2 => ("output/day_12_route.svg", route.to_svg()),
+        _ => return,
+    };
+
+    match fs::create_dir_all("output").and_then(|_| fs::write(path, contents)) {
+        Ok(()) => println!("Wrote {} steps to {}", route.steps.len(), path),
+        Err(error) => println!("Error Writing Route: {}", error),
+    }
+}
+
+fn undo_actions(ship: &mut Ship, actions: &[Action]) {
+    print!("Undo how many of the last part 2 actions: ");
+
+    let count = (get_user_input() as usize).min(actions.len());
+
+    if count == 0 {
+        return;
+    }
+
+    for action in actions.iter().rev().take(count) {
+        if let Err(error) = ship.undo(action, &WaypointModel) {
+            println!("Error Undoing {:?}: {}", action, error);
+
+            return;
+        }
+    }
+
+    println!(
+        "Manhattan distance after undoing {} actions: {}",
+        count,
+        ship.manhattan_distance_moved()
+    );
+}
+
+fn run_script() {
+    print!(
+        "Run the navigation script (0 = no, 1 = direct, 2 = waypoint, 3 = relative waypoint, \
+         4 = velocity): "
+    );
+
+    let direct = DirectModel::new(Compass::EightWay);
+
+    let model: &dyn NavigationModel = match get_user_input() {
+        1 => &direct,
+        2 => &WaypointModel,
+        3 => &RelativeWaypointModel,
+        4 => &VelocityModel,
+        _ => return,
+    };
+
+    let result = to_string_vector("inputs/day_12_script.txt")
+        .and_then(|lines| script::parse_script(&lines))
+        .and_then(|actions| {
+            let mut ship = Ship::new();
+
+            ship.navigate(&actions, model)?;
+
+            Ok(ship)
+        });
+
+    match result {
+        Ok(ship) => {
+            println!(
+                "Navigation Script: {} from the start",
+                ship.manhattan_distance_moved()
+            );
+
+            print_route_summary("Script", ship.get_route());
+        }
+        Err(error) => println!("Error Running Script: {}", error),
+    }
+}
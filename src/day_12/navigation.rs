@@ -0,0 +1,199 @@
+use crate::util::location::Location;
+use crate::util::point_2d::Point2d;
+
+use super::heading::{self, Compass, Heading};
+
+/// The parts of the ship that navigation models move around.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ShipState {
+    pub facing: Heading,
+    pub location: Point2d<i32>,
+    pub waypoint: Point2d<i32>,
+    pub velocity: Point2d<i32>,
+}
+
+impl ShipState {
+    pub fn new() -> ShipState {
+        ShipState {
+            facing: Heading::EAST,
+            location: Point2d::new(0, 0),
+            waypoint: Point2d::new(10, 1),
+            velocity: Point2d::new(0, 0),
+        }
+    }
+}
+
+/// Decides what the single letter actions do to the ship.
+pub trait NavigationModel {
+    /// `N`, `S`, `E` and `W`.
+    fn shift(&self, state: &mut ShipState, offset: Point2d<i32>);
+
+    /// `L` and `R`, counter-clockwise by `degrees`.
+    fn rotate(&self, state: &mut ShipState, degrees: i32) -> Result<(), String>;
+
+    /// `F`; a negative value has to undo a positive one.
+    fn forward(&self, state: &mut ShipState, value: i32) -> Result<(), String>;
+}
+
+/// The part 1 rules: the ship moves and turns itself.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DirectModel {
+    compass: Compass,
+}
+
+impl DirectModel {
+    pub fn new(compass: Compass) -> DirectModel {
+        DirectModel { compass }
+    }
+}
+
+impl NavigationModel for DirectModel {
+    fn shift(&self, state: &mut ShipState, offset: Point2d<i32>) {
+        state.location = state.location.add(&offset);
+    }
+
+    fn rotate(&self, state: &mut ShipState, degrees: i32) -> Result<(), String> {
+        self.compass.check_rotation(degrees)?;
+
+        state.facing = state.facing.rotate(degrees);
+
+        Ok(())
+    }
+
+    fn forward(&self, state: &mut ShipState, value: i32) -> Result<(), String> {
+        let step = state
+            .facing
+            .get_step()
+            .ok_or(format!("Cannot Move Forward Facing {}", state.facing))?;
+
+        state.location = state.location.add(&scale(step, value));
+
+        Ok(())
+    }
+}
+
+/// The part 2 rules: actions move the waypoint around the ship.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct WaypointModel;
+
+impl NavigationModel for WaypointModel {
+    fn shift(&self, state: &mut ShipState, offset: Point2d<i32>) {
+        state.waypoint = state.waypoint.add(&offset);
+    }
+
+    fn rotate(&self, state: &mut ShipState, degrees: i32) -> Result<(), String> {
+        state.waypoint = heading::rotate_point(state.waypoint, degrees)?;
+
+        Ok(())
+    }
+
+    fn forward(&self, state: &mut ShipState, value: i32) -> Result<(), String> {
+        state.location = state.location.add(&scale(state.waypoint, value));
+
+        Ok(())
+    }
+}
+
+/// The waypoint is seen from the bridge: east is straight ahead and north is to port. Turning
+/// turns the ship, and the waypoint with it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RelativeWaypointModel;
+
+impl NavigationModel for RelativeWaypointModel {
+    fn shift(&self, state: &mut ShipState, offset: Point2d<i32>) {
+        state.waypoint = state.waypoint.add(&offset);
+    }
+
+    fn rotate(&self, state: &mut ShipState, degrees: i32) -> Result<(), String> {
+        Compass::FourWay.check_rotation(degrees)?;
+
+        state.facing = state.facing.rotate(degrees);
+
+        Ok(())
+    }
+
+    fn forward(&self, state: &mut ShipState, value: i32) -> Result<(), String> {
+        let waypoint = heading::rotate_point(state.waypoint, state.facing.get_degrees())?;
+
+        state.location = state.location.add(&scale(waypoint, value));
+
+        Ok(())
+    }
+}
+
+/// The waypoint is the acceleration of the ship, which starts at rest. Every unit forward
+/// adds the acceleration to the velocity, then the velocity to the location.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct VelocityModel;
+
+impl NavigationModel for VelocityModel {
+    fn shift(&self, state: &mut ShipState, offset: Point2d<i32>) {
+        state.waypoint = state.waypoint.add(&offset);
+    }
+
+    fn rotate(&self, state: &mut ShipState, degrees: i32) -> Result<(), String> {
+        state.waypoint = heading::rotate_point(state.waypoint, degrees)?;
+
+        Ok(())
+    }
+
+    fn forward(&self, state: &mut ShipState, value: i32) -> Result<(), String> {
+        for _ in 0..value.abs() {
+            if 0 < value {
+                state.velocity = state.velocity.add(&state.waypoint);
+                state.location = state.location.add(&state.velocity);
+            } else {
+                state.location = state.location.add(&scale(state.velocity, -1));
+                state.velocity = state.velocity.add(&scale(state.waypoint, -1));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn scale(point: Point2d<i32>, value: i32) -> Point2d<i32> {
+    Point2d::new(value * point.x, value * point.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_waypoint_model() {
+        let mut state = ShipState::new();
+
+        RelativeWaypointModel.rotate(&mut state, -90).unwrap();
+        RelativeWaypointModel.shift(&mut state, Point2d::new(0, 2));
+        RelativeWaypointModel.forward(&mut state, 2).unwrap();
+
+        let result_rotation = RelativeWaypointModel.rotate(&mut state, 45);
+
+        assert_eq!(state.location, Point2d::new(6, -20));
+        assert_eq!(state.waypoint, Point2d::new(10, 3));
+        assert!(result_rotation.is_err());
+    }
+
+    #[test]
+    fn test_velocity_model() {
+        let mut state = ShipState::new();
+
+        state.waypoint = Point2d::new(1, 0);
+
+        VelocityModel.forward(&mut state, 3).unwrap();
+
+        let result_location = state.location;
+        let result_velocity = state.velocity;
+
+        VelocityModel.forward(&mut state, -3).unwrap();
+
+        let mut expected = ShipState::new();
+
+        expected.waypoint = Point2d::new(1, 0);
+
+        assert_eq!(result_location, Point2d::new(6, 0));
+        assert_eq!(result_velocity, Point2d::new(3, 0));
+        assert_eq!(state, expected);
+    }
+}
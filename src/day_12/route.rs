@@ -0,0 +1,200 @@
+use std::cmp::{max, min};
+
+use crate::util::location::Location;
+use crate::util::point_2d::Point2d;
+
+use super::heading::Heading;
+
+/// Where the ship is after an action, and where it is heading.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RouteStep {
+    pub location: Point2d<i32>,
+    pub facing: Heading,
+    pub waypoint: Point2d<i32>,
+}
+
+/// Every position of the ship, starting with the one before the first action.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Route {
+    pub steps: Vec<RouteStep>,
+}
+
+impl Route {
+    pub fn new(start: RouteStep) -> Route {
+        Route { steps: vec![start] }
+    }
+
+    pub fn push(&mut self, step: RouteStep) {
+        self.steps.push(step);
+    }
+
+    /// The location with the largest manhattan distance from the start, and that distance.
+    pub fn farthest_point(&self) -> Option<(Point2d<i32>, i32)> {
+        let start = self.steps.first()?.location;
+
+        self.steps
+            .iter()
+            .map(|step| (step.location, start.manhattan_distance_to(&step.location)))
+            .max_by_key(|&(_, distance)| distance)
+    }
+
+    /// The manhattan distances between consecutive locations added up.
+    pub fn total_distance(&self) -> i32 {
+        self.steps
+            .windows(2)
+            .map(|pair| pair[0].location.manhattan_distance_to(&pair[1].location))
+            .sum()
+    }
+
+    /// The lower left and upper right corner of the area the ship passed through.
+    pub fn bounding_box(&self) -> Option<(Point2d<i32>, Point2d<i32>)> {
+        let start = self.steps.first()?.location;
+
+        Some(
+            self.steps
+                .iter()
+                .fold((start, start), |(lower, upper), step| {
+                    (
+                        Point2d::new(min(lower.x, step.location.x), min(lower.y, step.location.y)),
+                        Point2d::new(max(upper.x, step.location.x), max(upper.y, step.location.y)),
+                    )
+                }),
+        )
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,x,y,facing,waypoint_x,waypoint_y\n");
+
+        for (index, step) in self.steps.iter().enumerate() {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                index,
+                step.location.x,
+                step.location.y,
+                step.facing,
+                step.waypoint.x,
+                step.waypoint.y
+            ));
+        }
+
+        csv
+    }
+
+    /// Draws the route as a polyline with north pointing up, marking the start in green and the
+    /// end in red.
+    pub fn to_svg(&self) -> String {
+        let (lower, upper) = match self.bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => (Point2d::new(0, 0), Point2d::new(0, 0)),
+        };
+
+        let margin = max(max(upper.x - lower.x, upper.y - lower.y) / 20, 1);
+        let stroke_width = margin as f64 / 4.0;
+
+        let points: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| format!("{},{}", step.location.x, -step.location.y))
+            .collect();
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            lower.x - margin,
+            -upper.y - margin,
+            upper.x - lower.x + 2 * margin,
+            upper.y - lower.y + 2 * margin
+        );
+
+        svg.push_str(&format!(
+            "  <polyline fill=\"none\" stroke=\"black\" stroke-width=\"{}\" points=\"{}\"/>\n",
+            stroke_width,
+            points.join(" ")
+        ));
+
+        let markers = [(self.steps.first(), "green"), (self.steps.last(), "red")];
+
+        for (step, color) in markers.iter() {
+            if let Some(step) = step {
+                svg.push_str(&format!(
+                    "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+                    step.location.x,
+                    -step.location.y,
+                    stroke_width * 2.0,
+                    color
+                ));
+            }
+        }
+
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: i32, y: i32) -> RouteStep {
+        RouteStep {
+            location: Point2d::new(x, y),
+            facing: Heading::EAST,
+            waypoint: Point2d::new(10, 1),
+        }
+    }
+
+    #[test]
+    fn test_route_queries() {
+        let mut route = Route::new(step(0, 0));
+
+        route.push(step(10, 0));
+        route.push(step(10, 3));
+        route.push(step(-4, 3));
+
+        let result_farthest = route.farthest_point();
+        let result_distance = route.total_distance();
+        let result_bounding_box = route.bounding_box();
+
+        assert_eq!(result_farthest, Some((Point2d::new(10, 3), 13)));
+        assert_eq!(result_distance, 27);
+        assert_eq!(
+            result_bounding_box,
+            Some((Point2d::new(-4, 0), Point2d::new(10, 3)))
+        );
+    }
+
+    #[test]
+    fn test_route_to_csv() {
+        let mut route = Route::new(step(0, 0));
+
+        route.push(step(10, -2));
+
+        let result = route.to_csv();
+
+        let expected = "step,x,y,facing,waypoint_x,waypoint_y
+0,0,0,E,10,1
+1,10,-2,E,10,1
+";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_route_to_svg() {
+        let mut route = Route::new(step(0, 0));
+
+        route.push(step(4, -2));
+        route.push(step(6, 3));
+
+        let result = route.to_svg();
+
+        let expected = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -4 8 7\">
+  <polyline fill=\"none\" stroke=\"black\" stroke-width=\"0.25\" points=\"0,0 4,2 6,-3\"/>
+  <circle cx=\"0\" cy=\"0\" r=\"0.5\" fill=\"green\"/>
+  <circle cx=\"6\" cy=\"-3\" r=\"0.5\" fill=\"red\"/>
+</svg>
+";
+
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use crate::util::point_2d::Point2d;
+
+use super::ship::Action;
+
+/// Parses a navigation script. Besides the single letter actions a line can hold
+///
+/// - `# comment`, also after an action
+/// - `repeat N {` to start a block that ends at a line with just `}`
+/// - `preset NAME X Y` to name a waypoint
+/// - `waypoint NAME` or `waypoint X Y` to set the waypoint
+/// - `goto X Y` to move the ship straight to a location
+/// - `reverse` to turn the ship, or the waypoint, around
+pub fn parse_script(lines: &[String]) -> Result<Vec<Action>, String> {
+    let mut presets = HashMap::new();
+    let mut blocks = vec![(1, Vec::new())];
+
+    for (line_number, line) in lines.iter().enumerate() {
+        parse_line(line, &mut presets, &mut blocks)
+            .map_err(|error| format!("Line {}: {}", line_number + 1, error))?;
+    }
+
+    match blocks.pop() {
+        Some((_, actions)) if blocks.is_empty() => Ok(actions),
+        _ => Err(String::from("Repeat Block Is Never Closed")),
+    }
+}
+
+fn parse_line(
+    line: &str,
+    presets: &mut HashMap<String, Point2d<i32>>,
+    blocks: &mut Vec<(usize, Vec<Action>)>,
+) -> Result<(), String> {
+    let words: Vec<&str> = line
+        .split('#')
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .collect();
+
+    let action = match words.as_slice() {
+        [] => return Ok(()),
+        ["repeat", count, "{"] => {
+            let count = count
+                .parse()
+                .map_err(|_| format!("Invalid Repeat Count: {}", count))?;
+
+            blocks.push((count, Vec::new()));
+
+            return Ok(());
+        }
+        ["}"] => {
+            if blocks.len() == 1 {
+                return Err(String::from("No Repeat Block To Close"));
+            }
+
+            let (count, actions) = blocks.pop().unwrap();
+
+            Action::Repeat(count, actions)
+        }
+        ["preset", name, x, y] => {
+            presets.insert(name.to_string(), parse_point(x, y)?);
+
+            return Ok(());
+        }
+        ["waypoint", name] => Action::SetWaypoint(
+            *presets
+                .get(*name)
+                .ok_or(format!("No Waypoint Preset Named: {}", name))?,
+        ),
+        ["waypoint", x, y] => Action::SetWaypoint(parse_point(x, y)?),
+        ["goto", x, y] => Action::GoTo(parse_point(x, y)?),
+        ["reverse"] => Action::Reverse,
+        [action] => Action::parse(action)?,
+        _ => return Err(format!("Cannot Parse: {}", line.trim())),
+    };
+
+    blocks.last_mut().unwrap().1.push(action);
+
+    Ok(())
+}
+
+fn parse_point(x: &str, y: &str) -> Result<Point2d<i32>, String> {
+    match (x.parse(), y.parse()) {
+        (Ok(x), Ok(y)) => Ok(Point2d::new(x, y)),
+        _ => Err(format!("Invalid Point: {} {}", x, y)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SCRIPT: [&str; 12] = [
+        "# sail a square, then head home",
+        "preset home 10 1",
+        "repeat 4 {",
+        "    F10 # one side",
+        "    L90",
+        "}",
+        "",
+        "waypoint 0 5",
+        "reverse",
+        "goto 3 -4",
+        "waypoint home",
+        "F1",
+    ];
+
+    #[test]
+    fn test_parse_script() {
+        let lines: Vec<String> = TEST_SCRIPT.iter().map(|s| s.to_string()).collect();
+
+        let result = parse_script(&lines);
+
+        let expected = Ok(vec![
+            Action::Repeat(4, vec![Action::Forward(10), Action::Left(90)]),
+            Action::SetWaypoint(Point2d::new(0, 5)),
+            Action::Reverse,
+            Action::GoTo(Point2d::new(3, -4)),
+            Action::SetWaypoint(Point2d::new(10, 1)),
+            Action::Forward(1),
+        ]);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_parse_script_errors() {
+        let to_lines =
+            |lines: &[&str]| -> Vec<String> { lines.iter().map(|s| s.to_string()).collect() };
+
+        let result_1 = parse_script(&to_lines(&["repeat 2 {", "F10"]));
+        let result_2 = parse_script(&to_lines(&["F10", "}"]));
+        let result_3 = parse_script(&to_lines(&["waypoint harbour"]));
+        let result_4 = parse_script(&to_lines(&["goto 1"]));
+
+        assert_eq!(result_1, Err(String::from("Repeat Block Is Never Closed")));
+        assert_eq!(
+            result_2,
+            Err(String::from("Line 2: No Repeat Block To Close"))
+        );
+        assert_eq!(
+            result_3,
+            Err(String::from("Line 1: No Waypoint Preset Named: harbour"))
+        );
+        assert!(result_4.is_err());
+    }
+}
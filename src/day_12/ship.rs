@@ -1,13 +1,8 @@
 use crate::util::location::Location;
 use crate::util::point_2d::Point2d;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
+use super::navigation::{NavigationModel, ShipState};
+use super::route::{Route, RouteStep};
 
 #[derive(Debug, PartialEq)]
 pub enum Action {
@@ -18,191 +13,156 @@ pub enum Action {
     Left(i32),
     Right(i32),
     Forward(i32),
+    /// Turns the ship, or the waypoint, around.
+    Reverse,
+    /// Moves the ship straight to the given location.
+    GoTo(Point2d<i32>),
+    SetWaypoint(Point2d<i32>),
+    Repeat(usize, Vec<Action>),
 }
 
 impl Action {
     pub fn new(info: &str) -> Action {
-        let (action_str, value_str) = info.split_at(1);
-
-        let value = value_str.parse().expect(info);
-
-        match action_str {
-            "N" => Action::North(value),
-            "S" => Action::South(value),
-            "E" => Action::East(value),
-            "W" => Action::West(value),
-            "L" => Action::Left(value),
-            "R" => Action::Right(value),
-            "F" => Action::Forward(value),
-            _ => panic!("No Action For: {}", info),
+        Action::parse(info).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Parses a single letter action like `F10`.
+    pub fn parse(info: &str) -> Result<Action, String> {
+        let mut chars = info.chars();
+
+        let action_char = chars.next().ok_or("No Action For An Empty Line")?;
+
+        let value = chars
+            .as_str()
+            .parse()
+            .map_err(|_| format!("Invalid Value For: {}", info))?;
+
+        match action_char {
+            'N' => Ok(Action::North(value)),
+            'S' => Ok(Action::South(value)),
+            'E' => Ok(Action::East(value)),
+            'W' => Ok(Action::West(value)),
+            'L' => Ok(Action::Left(value)),
+            'R' => Ok(Action::Right(value)),
+            'F' => Ok(Action::Forward(value)),
+            _ => Err(format!("No Action For: {}", info)),
+        }
+    }
+
+    /// The action that takes the ship back to where it was before this one. Absolute moves
+    /// cannot be undone since they do not remember where the ship was.
+    pub fn inverse(&self) -> Result<Action, String> {
+        match self {
+            Action::North(value) => Ok(Action::South(*value)),
+            Action::South(value) => Ok(Action::North(*value)),
+            Action::East(value) => Ok(Action::West(*value)),
+            Action::West(value) => Ok(Action::East(*value)),
+            Action::Left(value) => Ok(Action::Right(*value)),
+            Action::Right(value) => Ok(Action::Left(*value)),
+            Action::Forward(value) => value
+                .checked_neg()
+                .map(Action::Forward)
+                .ok_or(format!("Cannot Undo Moving Forward By {}", value)),
+            Action::Reverse => Ok(Action::Reverse),
+            Action::GoTo(_) | Action::SetWaypoint(_) => {
+                Err(format!("Cannot Undo An Absolute Move: {:?}", self))
+            }
+            Action::Repeat(count, actions) => {
+                let inverses: Result<Vec<Action>, String> = actions
+                    .iter()
+                    .rev()
+                    .map(|action| action.inverse())
+                    .collect();
+
+                Ok(Action::Repeat(*count, inverses?))
+            }
         }
     }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Ship {
-    facing: Direction,
     origin: Point2d<i32>,
-    location: Point2d<i32>,
-    waypoint: Point2d<i32>,
+    state: ShipState,
+    route: Route,
 }
 
 impl Ship {
     pub fn new() -> Ship {
+        let state = ShipState::new();
+
         Ship {
-            facing: Direction::East,
-            origin: Point2d::new(0, 0),
-            location: Point2d::new(0, 0),
-            waypoint: Point2d::new(10, 1),
+            origin: state.location,
+            state,
+            route: Route::new(Ship::get_route_step(&state)),
         }
     }
 
-    pub fn navigate(&mut self, navigation_instructions: &[Action], use_waypoint: bool) {
+    /// Stops at the first action that cannot be carried out.
+    pub fn navigate(
+        &mut self,
+        navigation_instructions: &[Action],
+        model: &dyn NavigationModel,
+    ) -> Result<(), String> {
         for instruction in navigation_instructions {
-            match instruction {
-                Action::North(value) => self.move_north(*value, use_waypoint),
-                Action::South(value) => self.move_south(*value, use_waypoint),
-                Action::East(value) => self.move_east(*value, use_waypoint),
-                Action::West(value) => self.move_west(*value, use_waypoint),
-                Action::Left(value) => self.rotate_left(*value, use_waypoint),
-                Action::Right(value) => self.rotate_right(*value, use_waypoint),
-                Action::Forward(value) => self.move_forward(*value, use_waypoint),
-            }
+            self.apply(instruction, model)?;
         }
-    }
-
-    pub fn manhattan_distance_moved(&self) -> i32 {
-        let relative_x = self.location.x - self.origin.x;
-        let relative_y = self.location.y - self.origin.y;
 
-        relative_x.abs() + relative_y.abs()
+        Ok(())
     }
 
-    pub fn reset(&mut self) {
-        self.facing = Direction::East;
-        self.location = self.origin;
-        self.waypoint = Point2d::new(10, 1);
+    /// Carries out the inverse of `action`, which takes the ship back to where it was before
+    /// `action`. The route keeps both.
+    pub fn undo(&mut self, action: &Action, model: &dyn NavigationModel) -> Result<(), String> {
+        self.apply(&action.inverse()?, model)
     }
 
-    fn move_north(&mut self, value: i32, use_waypoint: bool) {
-        if use_waypoint {
-            self.waypoint = self.waypoint.add(&Point2d::new(0, value));
-        } else {
-            self.location = self.location.add(&Point2d::new(0, value));
-        }
+    /// Every position and heading since the last reset.
+    pub fn get_route(&self) -> &Route {
+        &self.route
     }
 
-    fn move_south(&mut self, value: i32, use_waypoint: bool) {
-        if use_waypoint {
-            self.waypoint = self.waypoint.add(&Point2d::new(0, -value));
-        } else {
-            self.location = self.location.add(&Point2d::new(0, -value));
-        }
+    pub fn manhattan_distance_moved(&self) -> i32 {
+        self.origin.manhattan_distance_to(&self.state.location)
     }
 
-    fn move_east(&mut self, value: i32, use_waypoint: bool) {
-        if use_waypoint {
-            self.waypoint = self.waypoint.add(&Point2d::new(value, 0));
-        } else {
-            self.location = self.location.add(&Point2d::new(value, 0));
-        }
+    pub fn reset(&mut self) {
+        *self = Ship::new();
     }
 
-    fn move_west(&mut self, value: i32, use_waypoint: bool) {
-        if use_waypoint {
-            self.waypoint = self.waypoint.add(&Point2d::new(-value, 0));
-        } else {
-            self.location = self.location.add(&Point2d::new(-value, 0));
+    fn apply(&mut self, instruction: &Action, model: &dyn NavigationModel) -> Result<(), String> {
+        let state = &mut self.state;
+
+        match instruction {
+            Action::North(value) => model.shift(state, Point2d::new(0, *value)),
+            Action::South(value) => model.shift(state, Point2d::new(0, -value)),
+            Action::East(value) => model.shift(state, Point2d::new(*value, 0)),
+            Action::West(value) => model.shift(state, Point2d::new(-value, 0)),
+            Action::Left(value) => model.rotate(state, *value)?,
+            Action::Right(value) => model.rotate(state, -value)?,
+            Action::Forward(value) => model.forward(state, *value)?,
+            Action::Reverse => model.rotate(state, 180)?,
+            Action::GoTo(location) => state.location = *location,
+            Action::SetWaypoint(waypoint) => state.waypoint = *waypoint,
+            Action::Repeat(count, actions) => {
+                for _ in 0..*count {
+                    self.navigate(actions, model)?;
+                }
+
+                return Ok(());
+            }
         }
-    }
 
-    fn rotate_left(&mut self, value: i32, use_waypoint: bool) {
-        let normalized_value = value % 360;
-
-        if use_waypoint {
-            self.waypoint = match normalized_value {
-                0 => self.waypoint,
-                90 => Point2d::new(-self.waypoint.y, self.waypoint.x),
-                180 => Point2d::new(-self.waypoint.x, -self.waypoint.y),
-                270 => Point2d::new(self.waypoint.y, -self.waypoint.x),
-                _ => panic!("Cannot Rotate to Value: {:?}", normalized_value),
-            };
-        } else {
-            self.facing = match normalized_value {
-                0 => self.facing,
-                90 => match self.facing {
-                    Direction::North => Direction::West,
-                    Direction::South => Direction::East,
-                    Direction::East => Direction::North,
-                    Direction::West => Direction::South,
-                },
-                180 => match self.facing {
-                    Direction::North => Direction::South,
-                    Direction::South => Direction::North,
-                    Direction::East => Direction::West,
-                    Direction::West => Direction::East,
-                },
-                270 => match self.facing {
-                    Direction::North => Direction::East,
-                    Direction::South => Direction::West,
-                    Direction::East => Direction::South,
-                    Direction::West => Direction::North,
-                },
-                _ => panic!("Cannot Rotate to Value: {:?}", normalized_value),
-            };
-        }
-    }
+        self.route.push(Ship::get_route_step(&self.state));
 
-    fn rotate_right(&mut self, value: i32, use_waypoint: bool) {
-        let normalized_value = value % 360;
-
-        if use_waypoint {
-            self.waypoint = match normalized_value {
-                0 => self.waypoint,
-                90 => Point2d::new(self.waypoint.y, -self.waypoint.x),
-                180 => Point2d::new(-self.waypoint.x, -self.waypoint.y),
-                270 => Point2d::new(-self.waypoint.y, self.waypoint.x),
-                _ => panic!("Cannot Rotate to Value: {:?}", normalized_value),
-            };
-        } else {
-            self.facing = match normalized_value {
-                0 => self.facing,
-                90 => match self.facing {
-                    Direction::North => Direction::East,
-                    Direction::South => Direction::West,
-                    Direction::East => Direction::South,
-                    Direction::West => Direction::North,
-                },
-                180 => match self.facing {
-                    Direction::North => Direction::South,
-                    Direction::South => Direction::North,
-                    Direction::East => Direction::West,
-                    Direction::West => Direction::East,
-                },
-                270 => match self.facing {
-                    Direction::North => Direction::West,
-                    Direction::South => Direction::East,
-                    Direction::East => Direction::North,
-                    Direction::West => Direction::South,
-                },
-                _ => panic!("Cannot Rotate to Value: {:?}", normalized_value),
-            };
-        }
+        Ok(())
     }
 
-    fn move_forward(&mut self, value: i32, to_waypoint: bool) {
-        if to_waypoint {
-            self.location = self.location.add(&Point2d::new(
-                value * self.waypoint.x,
-                value * self.waypoint.y,
-            ));
-        } else {
-            match self.facing {
-                Direction::North => self.move_north(value, false),
-                Direction::South => self.move_south(value, false),
-                Direction::East => self.move_east(value, false),
-                Direction::West => self.move_west(value, false),
-            }
+    fn get_route_step(state: &ShipState) -> RouteStep {
+        RouteStep {
+            location: state.location,
+            facing: state.facing,
+            waypoint: state.waypoint,
         }
     }
 }
@@ -210,6 +170,10 @@ impl Ship {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day_12::heading::{Compass, Heading};
+    use crate::day_12::navigation::{
+        DirectModel, RelativeWaypointModel, VelocityModel, WaypointModel,
+    };
 
     const TEST_DATA: [&str; 5] = ["F10", "N3", "F7", "R90", "F11"];
 
@@ -228,22 +192,81 @@ mod tests {
         assert_eq!(results, expected);
     }
 
+    #[test]
+    fn test_action_parse_errors() {
+        assert!(Action::parse("").is_err());
+        assert!(Action::parse("F").is_err());
+        assert!(Action::parse("X10").is_err());
+        assert!(Action::parse("é1").is_err());
+    }
+
+    #[test]
+    fn test_action_inverse() {
+        let action = Action::Repeat(2, vec![Action::Forward(3), Action::Left(90)]);
+
+        let result_1 = action.inverse();
+        let result_2 = Action::GoTo(Point2d::new(1, 1)).inverse();
+        let result_3 = Action::Forward(i32::MIN).inverse();
+
+        let expected_1 = Ok(Action::Repeat(
+            2,
+            vec![Action::Right(90), Action::Forward(-3)],
+        ));
+
+        assert_eq!(result_1, expected_1);
+        assert!(result_2.is_err());
+        assert!(result_3.is_err());
+    }
+
     #[test]
     fn test_ship_navigate() {
         let actions: Vec<Action> = TEST_DATA.iter().map(|s| Action::new(s)).collect();
 
         let mut ship = Ship::new();
 
-        ship.navigate(&actions, false);
+        ship.navigate(&actions, &DirectModel::new(Compass::FourWay))
+            .unwrap();
 
-        let expected = Ship {
-            facing: Direction::South,
-            origin: Point2d::new(0, 0),
+        let expected = RouteStep {
+            facing: Heading::new(270),
             location: Point2d::new(17, -8),
             waypoint: Point2d::new(10, 1),
         };
 
-        assert_eq!(ship, expected);
+        assert_eq!(Ship::get_route_step(&ship.state), expected);
+        assert_eq!(ship.origin, Point2d::new(0, 0));
+    }
+
+    #[test]
+    fn test_ship_get_route() {
+        let actions: Vec<Action> = TEST_DATA.iter().map(|s| Action::new(s)).collect();
+
+        let mut ship = Ship::new();
+
+        ship.navigate(&actions, &WaypointModel).unwrap();
+
+        let result: Vec<Point2d<i32>> = ship
+            .get_route()
+            .steps
+            .iter()
+            .map(|step| step.location)
+            .collect();
+
+        let expected = vec![
+            Point2d::new(0, 0),
+            Point2d::new(100, 10),
+            Point2d::new(100, 10),
+            Point2d::new(170, 38),
+            Point2d::new(170, 38),
+            Point2d::new(214, -72),
+        ];
+
+        assert_eq!(result, expected);
+        assert_eq!(ship.get_route().steps[4].waypoint, Point2d::new(4, -10));
+
+        ship.reset();
+
+        assert_eq!(ship.get_route().steps.len(), 1);
     }
 
     #[test]
@@ -252,7 +275,8 @@ mod tests {
 
         let mut ship = Ship::new();
 
-        ship.navigate(&actions, false);
+        ship.navigate(&actions, &DirectModel::new(Compass::FourWay))
+            .unwrap();
 
         let result = ship.manhattan_distance_moved();
 
@@ -267,7 +291,7 @@ mod tests {
 
         let mut ship = Ship::new();
 
-        ship.navigate(&actions, true);
+        ship.navigate(&actions, &WaypointModel).unwrap();
 
         let result = ship.manhattan_distance_moved();
 
@@ -275,4 +299,79 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_ship_navigate_compass() {
+        let actions = vec![Action::Left(45), Action::Forward(10), Action::Right(135)];
+
+        let mut ship = Ship::new();
+
+        let result_four_way = ship.navigate(&actions, &DirectModel::new(Compass::FourWay));
+
+        ship.reset();
+
+        let result_waypoint = ship.navigate(&actions, &WaypointModel);
+
+        ship.reset();
+        ship.navigate(&actions, &DirectModel::new(Compass::EightWay))
+            .unwrap();
+
+        assert!(result_four_way.is_err());
+        assert!(result_waypoint.is_err());
+        assert_eq!(ship.state.location, Point2d::new(10, 10));
+        assert_eq!(ship.state.facing, Heading::new(270));
+    }
+
+    #[test]
+    fn test_ship_undo() {
+        let actions: Vec<Action> = TEST_DATA.iter().map(|s| Action::new(s)).collect();
+
+        let direct = DirectModel::new(Compass::FourWay);
+        let models: Vec<&dyn NavigationModel> = vec![
+            &direct,
+            &WaypointModel,
+            &RelativeWaypointModel,
+            &VelocityModel,
+        ];
+
+        for model in models {
+            let mut ship = Ship::new();
+
+            ship.navigate(&actions, model).unwrap();
+
+            for action in actions.iter().rev() {
+                ship.undo(action, model).unwrap();
+            }
+
+            let expected = Ship::get_route_step(&Ship::new().state);
+
+            assert_eq!(Ship::get_route_step(&ship.state), expected);
+            assert_eq!(ship.get_route().steps.len(), 11);
+        }
+    }
+
+    #[test]
+    fn test_ship_navigate_extended_actions() {
+        let actions = vec![
+            Action::Repeat(2, vec![Action::Forward(5), Action::Reverse]),
+            Action::GoTo(Point2d::new(-3, 7)),
+            Action::SetWaypoint(Point2d::new(0, -1)),
+            Action::Forward(2),
+        ];
+
+        let mut ship = Ship::new();
+
+        ship.navigate(&actions, &DirectModel::new(Compass::FourWay))
+            .unwrap();
+
+        let result_direct = Ship::get_route_step(&ship.state);
+
+        ship.reset();
+        ship.navigate(&actions, &WaypointModel).unwrap();
+
+        assert_eq!(result_direct.location, Point2d::new(-1, 7));
+        assert_eq!(result_direct.facing, Heading::EAST);
+        assert_eq!(ship.state.location, Point2d::new(-3, 5));
+        assert_eq!(ship.get_route().steps.len(), 8);
+    }
 }
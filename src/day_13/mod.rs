@@ -1,17 +1,23 @@
 mod shuttle;
 
 use crate::util::file_reader::to_string_vector;
-use shuttle::ShuttleSystemCalculator;
+use crate::{get_user_input, wants_extras};
+
+use shuttle::{ShuttleSchedule, ShuttleSystemCalculator};
 
 pub fn run_day_13() {
     let file_input = to_string_vector("inputs/day_13.txt");
 
     match file_input {
         Ok(input_lines) => {
-            let shuttle_system_calculator = ShuttleSystemCalculator::new(&input_lines);
+            let mut shuttle_system_calculator = ShuttleSystemCalculator::new(&input_lines);
 
             part_1(&shuttle_system_calculator);
             part_2(&shuttle_system_calculator);
+
+            if wants_extras() {
+                explore_timetable(&mut shuttle_system_calculator);
+            }
         }
         Err(error) => println!("Error Parsing File: {:?}", error),
     };
@@ -39,4 +45,81 @@ fn part_2(shuttle_system_calculator: &ShuttleSystemCalculator) {
         .unwrap();
 
     println!("Day 13 Part 2: {}", part_2);
+
+    let constraints = shuttle_system_calculator.get_departure_constraints();
+
+    match shuttle_system_calculator.solve_departure_constraints(&constraints) {
+        Ok(solution) => println!(
+            "The departures line up again every {} minutes",
+            solution.period
+        ),
+        Err(error) => println!("Error Solving Departures: {}", error),
+    }
+}
+
+fn explore_timetable(shuttle_system_calculator: &mut ShuttleSystemCalculator) {
+    let time_in_minutes = shuttle_system_calculator.departure_time_in_minutes;
+
+    print!("Add a shuttle with a loop time of (0 = skip): ");
+
+    let loop_time_in_minutes = get_user_input() as u64;
+
+    if loop_time_in_minutes != 0 {
+        print!("First departure of the new shuttle: ");
+
+        let start_time_in_minutes = get_user_input() as u64;
+
+        let id = shuttle_system_calculator.next_free_id();
+
+        let shuttle = ShuttleSchedule::running(id, start_time_in_minutes, loop_time_in_minutes);
+
+        match shuttle.and_then(|shuttle| shuttle_system_calculator.add_shuttle(shuttle)) {
+            Ok(()) => println!("Added bus {}", id),
+            Err(error) => println!("Error Adding Shuttle: {}", error),
+        }
+    }
+
+    let ids: Vec<u64> = shuttle_system_calculator
+        .get_running_shuttles()
+        .map(|shuttle| shuttle.id)
+        .collect();
+
+    print!("Show how many departures of each shuttle (0 = skip): ");
+
+    let count = get_user_input() as usize;
+
+    if count != 0 {
+        for &id in &ids {
+            if let Some(departures) =
+                shuttle_system_calculator.get_next_departures(id, time_in_minutes, count)
+            {
+                println!("Bus {}: {:?}", id, departures);
+            }
+        }
+    }
+
+    print!("Show the departures in the next minutes (0 = skip): ");
+
+    let minutes = get_user_input() as u64;
+
+    for departure in
+        shuttle_system_calculator.get_timeline(time_in_minutes, time_in_minutes + minutes)
+    {
+        println!("{:>12}  bus {}", departure.time_in_minutes, departure.id);
+    }
+
+    print!("Find when the first shuttles depart together (number of shuttles, 0 = skip): ");
+
+    let count = get_user_input() as usize;
+
+    if count != 0 {
+        let ids = &ids[..count.min(ids.len())];
+
+        match shuttle_system_calculator.get_earliest_joint_departure(ids, time_in_minutes) {
+            Some(joint_departure) => {
+                println!("Buses {:?} depart together at {}", ids, joint_departure)
+            }
+            None => println!("Buses {:?} never depart together", ids),
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use crate::util::math::lcm;
+use std::iter;
+
+use crate::util::math::solve_congruences;
 
 #[derive(Debug, PartialEq)]
 enum ShuttleStatus {
@@ -6,6 +8,45 @@ enum ShuttleStatus {
     OutOfService,
 }
 
+/// A single departure on the timeline of several shuttles.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Departure {
+    pub time_in_minutes: u64,
+    pub id: u64,
+}
+
+/// Bus `id` has to depart `offset_in_minutes` after the timestamp.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DepartureConstraint {
+    pub id: u64,
+    pub offset_in_minutes: u64,
+}
+
+/// The constraints hold at `timestamp` and again every `period` minutes after it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DepartureSolution {
+    pub timestamp: u64,
+    pub period: u64,
+}
+
+impl DepartureSolution {
+    pub fn first_at_or_after(&self, time_in_minutes: u64) -> Result<u64, String> {
+        if self.timestamp < time_in_minutes {
+            (time_in_minutes - self.timestamp)
+                .div_ceil(self.period)
+                .checked_mul(self.period)
+                .and_then(|offset| offset.checked_add(self.timestamp))
+                .ok_or(format!(
+                    "No Timestamp After {} Fits In 64 Bits",
+                    time_in_minutes
+                ))
+        } else {
+            Ok(self.timestamp)
+        }
+    }
+}
+
+/// A shuttle departs first at `start_time_in_minutes` and then every `loop_time_in_minutes`.
 #[derive(Debug, PartialEq)]
 pub struct ShuttleSchedule {
     pub id: u64,
@@ -15,16 +56,46 @@ pub struct ShuttleSchedule {
 }
 
 impl ShuttleSchedule {
+    pub fn running(
+        id: u64,
+        start_time_in_minutes: u64,
+        loop_time_in_minutes: u64,
+    ) -> Result<ShuttleSchedule, String> {
+        if loop_time_in_minutes == 0 {
+            return Err(format!("Shuttle {} Needs A Loop Time", id));
+        }
+
+        Ok(ShuttleSchedule {
+            id,
+            start_time_in_minutes,
+            loop_time_in_minutes,
+            status: ShuttleStatus::Running,
+        })
+    }
+
     pub fn next_closest_departure_to(&self, time_in_minutes: u64) -> Option<u64> {
         if self.status == ShuttleStatus::OutOfService {
             None
-        } else if time_in_minutes % self.loop_time_in_minutes == 0 {
-            Some(time_in_minutes)
+        } else if time_in_minutes <= self.start_time_in_minutes {
+            Some(self.start_time_in_minutes)
         } else {
-            Some(((time_in_minutes / self.loop_time_in_minutes) + 1) * self.loop_time_in_minutes)
+            let loops =
+                (time_in_minutes - self.start_time_in_minutes).div_ceil(self.loop_time_in_minutes);
+
+            Some(self.start_time_in_minutes + loops * self.loop_time_in_minutes)
         }
     }
 
+    /// The first `count` departures at or after `time_in_minutes`.
+    pub fn next_departures(&self, time_in_minutes: u64, count: usize) -> Vec<u64> {
+        iter::successors(
+            self.next_closest_departure_to(time_in_minutes),
+            |departure| Some(departure + self.loop_time_in_minutes),
+        )
+        .take(count)
+        .collect()
+    }
+
     fn new(info: &str) -> ShuttleSchedule {
         if info != "x" {
             let value = info.parse().expect(info);
@@ -60,13 +131,13 @@ impl ShuttleSystemCalculator {
     pub fn new(info: &[String]) -> ShuttleSystemCalculator {
         let err = format!("Error Input: {:?}", info);
 
-        let departure_time_in_minutes = info.get(0).expect(&err).parse().expect(&err);
+        let departure_time_in_minutes = info.first().expect(&err).parse().expect(&err);
 
         let shuttles = info
             .get(1)
             .expect(&err)
             .split(",")
-            .map(|s| ShuttleSchedule::new(s))
+            .map(ShuttleSchedule::new)
             .collect();
 
         ShuttleSystemCalculator {
@@ -75,48 +146,145 @@ impl ShuttleSystemCalculator {
         }
     }
 
-    pub fn get_earliest_shuttle_to_airport(&self) -> Option<&ShuttleSchedule> {
-        self.shuttles
-            .iter()
-            .filter(|shuttle| shuttle.is_running())
-            .min_by_key(|shuttle| shuttle.next_closest_departure_to(self.departure_time_in_minutes))
+    /// Rejects a shuttle whose id is already taken, since it could never be looked up.
+    pub fn add_shuttle(&mut self, shuttle: ShuttleSchedule) -> Result<(), String> {
+        if self.get_shuttle(shuttle.id).is_some() {
+            return Err(format!("Shuttle {} Already Exists", shuttle.id));
+        }
+
+        self.shuttles.push(shuttle);
+
+        Ok(())
     }
 
-    pub fn get_timestamp_for_subsequent_departures(&self) -> Option<u64> {
-        let mut result = None;
+    /// One more than the largest id in use.
+    pub fn next_free_id(&self) -> u64 {
+        self.get_running_shuttles()
+            .map(|shuttle| shuttle.id + 1)
+            .max()
+            .unwrap_or(1)
+    }
 
-        if let Some(first_shuttle) = self.shuttles.get(0) {
-            let mut loop_timestamp = first_shuttle.loop_time_in_minutes;
+    pub fn get_running_shuttles(&self) -> impl Iterator<Item = &ShuttleSchedule> {
+        self.shuttles.iter().filter(|shuttle| shuttle.is_running())
+    }
 
-            let mut loop_timestamp_sum = loop_timestamp;
+    pub fn get_shuttle(&self, id: u64) -> Option<&ShuttleSchedule> {
+        self.get_running_shuttles().find(|shuttle| shuttle.id == id)
+    }
 
-            for (index, next_shuttle) in self.shuttles.iter().enumerate().skip(1) {
-                if !next_shuttle.is_running() {
-                    continue;
-                }
+    pub fn get_next_departures(
+        &self,
+        id: u64,
+        time_in_minutes: u64,
+        count: usize,
+    ) -> Option<Vec<u64>> {
+        self.get_shuttle(id)
+            .map(|shuttle| shuttle.next_departures(time_in_minutes, count))
+    }
 
-                let mut target_departure = loop_timestamp_sum + (index as u64);
+    /// Every departure of a running shuttle between the two times, both included.
+    pub fn get_timeline(&self, from_in_minutes: u64, to_in_minutes: u64) -> Vec<Departure> {
+        let mut timeline: Vec<Departure> = self
+            .get_running_shuttles()
+            .flat_map(|shuttle| {
+                iter::successors(
+                    shuttle.next_closest_departure_to(from_in_minutes),
+                    move |departure| Some(departure + shuttle.loop_time_in_minutes),
+                )
+                .take_while(move |&departure| departure <= to_in_minutes)
+                .map(move |departure| Departure {
+                    time_in_minutes: departure,
+                    id: shuttle.id,
+                })
+            })
+            .collect();
 
-                let mut next_earliest_departure_to_target = next_shuttle
-                    .next_closest_departure_to(target_departure)
-                    .unwrap();
+        timeline.sort();
 
-                while next_earliest_departure_to_target != target_departure {
-                    loop_timestamp_sum += loop_timestamp;
+        timeline
+    }
 
-                    target_departure = loop_timestamp_sum + (index as u64);
+    /// The earliest time at or after `from_in_minutes` at which all the given shuttles depart.
+    /// Returns `None` if one of them is not running or they never depart together.
+    pub fn get_earliest_joint_departure(&self, ids: &[u64], from_in_minutes: u64) -> Option<u64> {
+        let constraints: Vec<DepartureConstraint> = ids
+            .iter()
+            .map(|&id| DepartureConstraint {
+                id,
+                offset_in_minutes: 0,
+            })
+            .collect();
 
-                    next_earliest_departure_to_target = next_shuttle
-                        .next_closest_departure_to(target_departure)
-                        .unwrap();
-                }
+        let solution = self.solve_departure_constraints(&constraints).ok()?;
 
-                result = Some(loop_timestamp_sum);
-                loop_timestamp = lcm(loop_timestamp, next_shuttle.loop_time_in_minutes);
-            }
+        solution.first_at_or_after(from_in_minutes).ok()
+    }
+
+    /// One constraint per running shuttle, which has to depart as many minutes after the
+    /// timestamp as its position in the list.
+    pub fn get_departure_constraints(&self) -> Vec<DepartureConstraint> {
+        self.shuttles
+            .iter()
+            .enumerate()
+            .filter(|(_, shuttle)| shuttle.is_running())
+            .map(|(index, shuttle)| DepartureConstraint {
+                id: shuttle.id,
+                offset_in_minutes: index as u64,
+            })
+            .collect()
+    }
+
+    /// Finds the earliest timestamp at which every constraint holds, no shuttle departing
+    /// before its first departure.
+    pub fn solve_departure_constraints(
+        &self,
+        constraints: &[DepartureConstraint],
+    ) -> Result<DepartureSolution, String> {
+        let mut congruences = Vec::with_capacity(constraints.len());
+        let mut earliest = 0;
+
+        for constraint in constraints {
+            let shuttle = self
+                .get_shuttle(constraint.id)
+                .ok_or(format!("Bus {} Is Not Running", constraint.id))?;
+
+            let loop_time_in_minutes = shuttle.loop_time_in_minutes;
+
+            let remainder = (shuttle.start_time_in_minutes % loop_time_in_minutes
+                + loop_time_in_minutes
+                - constraint.offset_in_minutes % loop_time_in_minutes)
+                % loop_time_in_minutes;
+
+            congruences.push((remainder, loop_time_in_minutes));
+
+            earliest = earliest.max(
+                shuttle
+                    .start_time_in_minutes
+                    .saturating_sub(constraint.offset_in_minutes),
+            );
         }
 
-        result
+        let (timestamp, period) = solve_congruences(&congruences)
+            .ok_or("No Timestamp Satisfies Every Departure Constraint")?;
+
+        let solution = DepartureSolution { timestamp, period };
+
+        Ok(DepartureSolution {
+            timestamp: solution.first_at_or_after(earliest)?,
+            period,
+        })
+    }
+
+    pub fn get_earliest_shuttle_to_airport(&self) -> Option<&ShuttleSchedule> {
+        self.get_running_shuttles()
+            .min_by_key(|shuttle| shuttle.next_closest_departure_to(self.departure_time_in_minutes))
+    }
+
+    pub fn get_timestamp_for_subsequent_departures(&self) -> Option<u64> {
+        self.solve_departure_constraints(&self.get_departure_constraints())
+            .ok()
+            .map(|solution| solution.timestamp)
     }
 }
 
@@ -239,4 +407,146 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_shuttle_service_next_departures() {
+        let shuttle = ShuttleSchedule::running(7, 10, 7).unwrap();
+
+        let result_1 = shuttle.next_departures(0, 3);
+        let result_2 = shuttle.next_departures(18, 2);
+        let result_3 = ShuttleSchedule::new("x").next_departures(0, 3);
+
+        assert_eq!(result_1, vec![10, 17, 24]);
+        assert_eq!(result_2, vec![24, 31]);
+        assert!(result_3.is_empty());
+        assert!(ShuttleSchedule::running(7, 10, 0).is_err());
+    }
+
+    #[test]
+    fn test_shuttle_system_calculator_get_timeline() {
+        let input: Vec<String> = vec!["0", "5,x,3"].into_iter().map(String::from).collect();
+
+        let mut system_calculator = ShuttleSystemCalculator::new(&input);
+
+        system_calculator
+            .add_shuttle(ShuttleSchedule::running(4, 7, 4).unwrap())
+            .unwrap();
+
+        let result = system_calculator.get_timeline(5, 12);
+
+        let expected: Vec<Departure> =
+            vec![(5, 5), (6, 3), (7, 4), (9, 3), (10, 5), (11, 4), (12, 3)]
+                .into_iter()
+                .map(|(time_in_minutes, id)| Departure {
+                    time_in_minutes,
+                    id,
+                })
+                .collect();
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            system_calculator.get_next_departures(4, 0, 2),
+            Some(vec![7, 11])
+        );
+        assert_eq!(system_calculator.get_next_departures(6, 0, 2), None);
+    }
+
+    #[test]
+    fn test_shuttle_system_calculator_add_shuttle() {
+        let input: Vec<String> = vec!["0", "5,x,3"].into_iter().map(String::from).collect();
+
+        let mut system_calculator = ShuttleSystemCalculator::new(&input);
+
+        let result_1 = system_calculator.add_shuttle(ShuttleSchedule::running(5, 2, 7).unwrap());
+        let result_2 = system_calculator.next_free_id();
+
+        system_calculator
+            .add_shuttle(ShuttleSchedule::running(result_2, 2, 5).unwrap())
+            .unwrap();
+
+        assert!(result_1.is_err());
+        assert_eq!(result_2, 6);
+        assert_eq!(
+            system_calculator.get_next_departures(6, 0, 2),
+            Some(vec![2, 7])
+        );
+        assert_eq!(system_calculator.get_running_shuttles().count(), 3);
+    }
+
+    #[test]
+    fn test_shuttle_system_calculator_get_earliest_joint_departure() {
+        let input: Vec<String> = vec!["0", "4,x,3"].into_iter().map(String::from).collect();
+
+        let mut system_calculator = ShuttleSystemCalculator::new(&input);
+
+        system_calculator
+            .add_shuttle(ShuttleSchedule::running(6, 1, 6).unwrap())
+            .unwrap();
+        system_calculator
+            .add_shuttle(ShuttleSchedule::running(10, 25, 10).unwrap())
+            .unwrap();
+
+        let result_1 = system_calculator.get_earliest_joint_departure(&[4, 3], 1);
+        let result_2 = system_calculator.get_earliest_joint_departure(&[3, 10], 0);
+        let result_3 = system_calculator.get_earliest_joint_departure(&[4, 6], 0);
+        let result_4 = system_calculator.get_earliest_joint_departure(&[4, 7], 0);
+
+        assert_eq!(result_1, Some(12));
+        assert_eq!(result_2, Some(45));
+        assert_eq!(result_3, None);
+        assert_eq!(result_4, None);
+    }
+
+    #[test]
+    fn test_shuttle_system_calculator_solve_departure_constraints() {
+        let input: Vec<String> = vec!["0", "4,6,x,9"].into_iter().map(String::from).collect();
+
+        let mut system_calculator = ShuttleSystemCalculator::new(&input);
+
+        let constraint = |id, offset_in_minutes| DepartureConstraint {
+            id,
+            offset_in_minutes,
+        };
+
+        let result_1 =
+            system_calculator.solve_departure_constraints(&[constraint(4, 0), constraint(6, 2)]);
+        let result_2 =
+            system_calculator.solve_departure_constraints(&[constraint(4, 0), constraint(6, 1)]);
+        let result_3 = system_calculator.solve_departure_constraints(&[constraint(5, 0)]);
+        let result_4 = system_calculator.get_timestamp_for_subsequent_departures();
+
+        system_calculator
+            .add_shuttle(ShuttleSchedule::running(7, 30, 7).unwrap())
+            .unwrap();
+
+        let result_5 =
+            system_calculator.solve_departure_constraints(&[constraint(4, 0), constraint(7, 2)]);
+
+        let expected_1 = Ok(DepartureSolution {
+            timestamp: 4,
+            period: 12,
+        });
+        let expected_5 = Ok(DepartureSolution {
+            timestamp: 28,
+            period: 28,
+        });
+
+        assert_eq!(result_1, expected_1);
+        assert!(result_2.is_err());
+        assert_eq!(result_3, Err(String::from("Bus 5 Is Not Running")));
+        assert_eq!(result_4, None);
+        assert_eq!(result_5, expected_5);
+    }
+
+    #[test]
+    fn test_departure_solution_first_at_or_after() {
+        let solution = DepartureSolution {
+            timestamp: 5,
+            period: u64::MAX / 2,
+        };
+
+        assert_eq!(solution.first_at_or_after(3), Ok(5));
+        assert_eq!(solution.first_at_or_after(6), Ok(5 + u64::MAX / 2));
+        assert!(solution.first_at_or_after(u64::MAX).is_err());
+    }
 }
@@ -1,3 +1,5 @@
+mod report;
+mod rule;
 mod ticket;
 
 use crate::util::file_reader::to_string_vector;
@@ -9,12 +11,25 @@ pub fn run_day_16() {
     match file_input {
         Ok(input_lines) => {
             let ticket_scanner = TicketScanner::new(&input_lines);
+            let report = ticket_scanner.get_report();
+
+            if !report.is_fully_mapped() {
+                println!("{}", report);
+            }
 
             let part_1 = ticket_scanner.get_error_rate();
             let part_2 = run_part_2(&ticket_scanner);
 
             println!("Day 16 Part 1: {}", part_1);
+
             println!("Day 16 Part 2: {}", part_2);
+
+            if 0 < report.invalid_text_count() {
+                println!(
+                    "Day 16 Invalid Text Values (not in the error rate): {}",
+                    report.invalid_text_count()
+                );
+            }
         }
         Err(error) => println!("Error Parsing File: {:?}", error),
     };
@@ -27,7 +42,9 @@ fn run_part_2(ticket_scanner: &TicketScanner) -> usize {
 
     for (field_name, field_value) in ticket_fields.iter() {
         if field_name.starts_with("departure") {
-            result *= *field_value;
+            result *= field_value
+                .as_number()
+                .expect("Departure fields should be numeric!");
         }
     }
 
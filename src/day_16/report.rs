@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::rule::FieldValue;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvalidValue {
+    pub column: usize,
+    pub value: FieldValue,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TicketReport {
+    pub ticket_index: usize,
+    pub invalid_values: Vec<InvalidValue>,
+}
+
+impl TicketReport {
+    pub fn is_valid(&self) -> bool {
+        self.invalid_values.is_empty()
+    }
+
+    /// The invalid numbers added up. Text has no value to add, so invalid text values are
+    /// counted by `invalid_text_count` instead.
+    pub fn error_rate(&self) -> usize {
+        self.invalid_values
+            .iter()
+            .filter_map(|invalid| invalid.value.as_number())
+            .sum()
+    }
+
+    pub fn invalid_text_count(&self) -> usize {
+        self.invalid_values
+            .iter()
+            .filter(|invalid| invalid.value.as_number().is_none())
+            .count()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColumnReport {
+    pub column: usize,
+    pub candidate_fields: Vec<String>,
+    pub assigned_field: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MappingIssue {
+    NoCandidates {
+        column: usize,
+    },
+    Conflict {
+        field: String,
+        columns: Vec<usize>,
+    },
+    Ambiguous {
+        columns: Vec<usize>,
+        fields: Vec<String>,
+    },
+    UnmatchedField {
+        field: String,
+    },
+}
+
+impl fmt::Display for MappingIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MappingIssue::NoCandidates { column } => write!(
+                f,
+                "over-constrained: no field accepts every valid value in column {}",
+                column
+            ),
+            MappingIssue::Conflict { field, columns } => write!(
+                f,
+                "over-constrained: field {:?} is the only option for columns {:?}",
+                field, columns
+            ),
+            MappingIssue::Ambiguous { columns, fields } => write!(
+                f,
+                "under-constrained: columns {:?} could still be any of {:?}",
+                columns, fields
+            ),
+            MappingIssue::UnmatchedField { field } => {
+                write!(f, "over-constrained: field {:?} fits no column", field)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScannerReport {
+    pub tickets: Vec<TicketReport>,
+    pub columns: Vec<ColumnReport>,
+    pub issues: Vec<MappingIssue>,
+}
+
+impl ScannerReport {
+    pub fn error_rate(&self) -> usize {
+        self.tickets.iter().map(|ticket| ticket.error_rate()).sum()
+    }
+
+    pub fn invalid_text_count(&self) -> usize {
+        self.tickets
+            .iter()
+            .map(|ticket| ticket.invalid_text_count())
+            .sum()
+    }
+
+    pub fn is_fully_mapped(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn field_mapping(&self) -> HashMap<String, usize> {
+        self.columns
+            .iter()
+            .filter_map(|column| {
+                column
+                    .assigned_field
+                    .as_ref()
+                    .map(|field| (field.clone(), column.column))
+            })
+            .collect()
+    }
+
+    pub fn to_table(&self) -> String {
+        let ticket_rows: Vec<Vec<String>> = self
+            .tickets
+            .iter()
+            .map(|ticket| {
+                let invalid_values: Vec<String> = ticket
+                    .invalid_values
+                    .iter()
+                    .map(|invalid| format!("col {} = {}", invalid.column, invalid.value))
+                    .collect();
+
+                vec![
+                    ticket.ticket_index.to_string(),
+                    String::from(if ticket.is_valid() {
+                        "valid"
+                    } else {
+                        "invalid"
+                    }),
+                    invalid_values.join(", "),
+                ]
+            })
+            .collect();
+
+        let column_rows: Vec<Vec<String>> = self
+            .columns
+            .iter()
+            .map(|column| {
+                vec![
+                    column.column.to_string(),
+                    column.candidate_fields.join(", "),
+                    column
+                        .assigned_field
+                        .clone()
+                        .unwrap_or_else(|| String::from("-")),
+                ]
+            })
+            .collect();
+
+        let mut result = render_table(&["ticket", "status", "invalid values"], &ticket_rows);
+
+        result.push('\n');
+        result.push_str(&render_table(
+            &["column", "candidate fields", "assigned field"],
+            &column_rows,
+        ));
+
+        for issue in &self.issues {
+            result.push_str(&format!("\n{}", issue));
+        }
+
+        result
+    }
+}
+
+impl fmt::Display for ScannerReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_table())
+    }
+}
+
+fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let render_row = |cells: Vec<&str>| -> String {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+
+        format!("{}\n", padded.join(" | ").trim_end())
+    };
+
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+    let mut result = render_row(headers.to_vec());
+
+    result.push_str(&format!("{}\n", separator.join("-+-")));
+
+    for row in rows {
+        result.push_str(&render_row(row.iter().map(|cell| cell.as_str()).collect()));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table() {
+        let rows = vec![
+            vec![String::from("0"), String::from("valid")],
+            vec![String::from("12"), String::from("invalid")],
+        ];
+
+        let result = render_table(&["ticket", "status"], &rows);
+
+        let expected = "ticket | status\n\
+                        -------+--------\n\
+                        0      | valid\n\
+                        12     | invalid\n";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_ticket_report_error_rate() {
+        let report = TicketReport {
+            ticket_index: 3,
+            invalid_values: vec![
+                InvalidValue {
+                    column: 0,
+                    value: FieldValue::Number(4),
+                },
+                InvalidValue {
+                    column: 1,
+                    value: FieldValue::Text(String::from("red")),
+                },
+                InvalidValue {
+                    column: 2,
+                    value: FieldValue::Number(12),
+                },
+            ],
+        };
+
+        assert_eq!(report.error_rate(), 16);
+        assert_eq!(report.invalid_text_count(), 1);
+    }
+
+    #[test]
+    fn test_scanner_report_field_mapping() {
+        let report = ScannerReport {
+            tickets: Vec::new(),
+            columns: vec![
+                ColumnReport {
+                    column: 0,
+                    candidate_fields: vec![String::from("row")],
+                    assigned_field: Some(String::from("row")),
+                },
+                ColumnReport {
+                    column: 1,
+                    candidate_fields: vec![String::from("class"), String::from("seat")],
+                    assigned_field: None,
+                },
+            ],
+            issues: Vec::new(),
+        };
+
+        let result = report.field_mapping();
+
+        let expected = vec![(String::from("row"), 0)].into_iter().collect();
+
+        assert_eq!(result, expected);
+    }
+}
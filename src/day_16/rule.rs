@@ -0,0 +1,245 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use regex::Regex;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum FieldValue {
+    Number(usize),
+    Text(String),
+}
+
+impl FieldValue {
+    pub fn new(info: &str) -> FieldValue {
+        let trimmed_info = info.trim();
+
+        match trimmed_info.parse() {
+            Ok(number) => FieldValue::Number(number),
+            Err(_) => FieldValue::Text(String::from(trimmed_info)),
+        }
+    }
+
+    pub fn as_number(&self) -> Option<usize> {
+        match self {
+            FieldValue::Number(number) => Some(*number),
+            FieldValue::Text(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldValue::Number(number) => write!(f, "{}", number),
+            FieldValue::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// A single predicate from a rule line.
+///
+/// Supported forms are `a-b`, `a`, `>=a`, `<=b`, `/regex/` (matched against the whole value)
+/// and `{x, y, z}` (membership in a fixed set).
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Range(RangeInclusive<usize>),
+    Exact(usize),
+    AtLeast(usize),
+    AtMost(usize),
+    Pattern(Regex),
+    OneOf(Vec<String>),
+}
+
+impl Condition {
+    pub fn new(info: &str) -> Result<Condition, String> {
+        let info = info.trim();
+        let parse_error = |_| format!("Can't Parse Condition: {:?}", info);
+
+        if let Some(bound) = info.strip_prefix(">=") {
+            Ok(Condition::AtLeast(
+                bound.trim().parse().map_err(parse_error)?,
+            ))
+        } else if let Some(bound) = info.strip_prefix("<=") {
+            Ok(Condition::AtMost(
+                bound.trim().parse().map_err(parse_error)?,
+            ))
+        } else if 2 <= info.len() && info.starts_with('/') && info.ends_with('/') {
+            let pattern = format!("^(?:{})$", &info[1..info.len() - 1]);
+
+            Regex::new(&pattern)
+                .map(Condition::Pattern)
+                .map_err(|error| format!("Bad Pattern {:?}: {}", info, error))
+        } else if 2 <= info.len() && info.starts_with('{') && info.ends_with('}') {
+            Ok(Condition::OneOf(
+                info[1..info.len() - 1]
+                    .split(',')
+                    .map(|member| String::from(member.trim()))
+                    .filter(|member| !member.is_empty())
+                    .collect(),
+            ))
+        } else if let Some((start, end)) = info.split_once('-') {
+            let start = start.trim().parse().map_err(parse_error)?;
+            let end = end.trim().parse().map_err(parse_error)?;
+
+            Ok(Condition::Range(start..=end))
+        } else {
+            Ok(Condition::Exact(info.parse().map_err(parse_error)?))
+        }
+    }
+
+    pub fn matches(&self, value: &FieldValue) -> bool {
+        match (self, value) {
+            (Condition::Range(range), FieldValue::Number(number)) => range.contains(number),
+            (Condition::Exact(expected), FieldValue::Number(number)) => expected == number,
+            (Condition::AtLeast(bound), FieldValue::Number(number)) => bound <= number,
+            (Condition::AtMost(bound), FieldValue::Number(number)) => number <= bound,
+            (Condition::Pattern(regex), _) => regex.is_match(&value.to_string()),
+            (Condition::OneOf(members), _) => members.contains(&value.to_string()),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq for Condition {
+    fn eq(&self, other: &Condition) -> bool {
+        match (self, other) {
+            (Condition::Range(a), Condition::Range(b)) => a == b,
+            (Condition::Exact(a), Condition::Exact(b)) => a == b,
+            (Condition::AtLeast(a), Condition::AtLeast(b)) => a == b,
+            (Condition::AtMost(a), Condition::AtMost(b)) => a == b,
+            (Condition::Pattern(a), Condition::Pattern(b)) => a.as_str() == b.as_str(),
+            (Condition::OneOf(a), Condition::OneOf(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// The right hand side of a rule line: `alternative or alternative ... [except exclusion or ...]`.
+///
+/// A value follows the rule when it matches at least one alternative and none of the exclusions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rule {
+    alternatives: Vec<Condition>,
+    exclusions: Vec<Condition>,
+}
+
+impl Rule {
+    pub fn new(info: &str) -> Result<Rule, String> {
+        let (alternatives_info, exclusions_info) = match info.split_once(" except ") {
+            Some((alternatives_info, exclusions_info)) => {
+                (alternatives_info, Some(exclusions_info))
+            }
+            None => (info, None),
+        };
+
+        let alternatives = Self::parse_conditions(alternatives_info)?;
+        let exclusions = match exclusions_info {
+            Some(exclusions_info) => Self::parse_conditions(exclusions_info)?,
+            None => Vec::new(),
+        };
+
+        if alternatives.is_empty() {
+            return Err(format!("Rule Has No Conditions: {:?}", info));
+        }
+
+        Ok(Rule {
+            alternatives,
+            exclusions,
+        })
+    }
+
+    pub fn matches(&self, value: &FieldValue) -> bool {
+        self.alternatives
+            .iter()
+            .any(|condition| condition.matches(value))
+            && !self
+                .exclusions
+                .iter()
+                .any(|condition| condition.matches(value))
+    }
+
+    fn parse_conditions(info: &str) -> Result<Vec<Condition>, String> {
+        info.split(" or ")
+            .filter(|condition_info| !condition_info.trim().is_empty())
+            .map(Condition::new)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_value_new() {
+        assert_eq!(FieldValue::new("42"), FieldValue::Number(42));
+        assert_eq!(
+            FieldValue::new(" blu "),
+            FieldValue::Text(String::from("blu"))
+        );
+    }
+
+    #[test]
+    fn test_condition_new() {
+        assert_eq!(Condition::new("6-11"), Ok(Condition::Range(6..=11)));
+        assert_eq!(Condition::new("7"), Ok(Condition::Exact(7)));
+        assert_eq!(Condition::new(">=18"), Ok(Condition::AtLeast(18)));
+        assert_eq!(Condition::new("<= 65"), Ok(Condition::AtMost(65)));
+        assert_eq!(
+            Condition::new("{amb, blu}"),
+            Ok(Condition::OneOf(vec![
+                String::from("amb"),
+                String::from("blu")
+            ]))
+        );
+        assert_eq!(
+            Condition::new("/[0-9]{9}/"),
+            Ok(Condition::Pattern(Regex::new("^(?:[0-9]{9})$").unwrap()))
+        );
+        assert!(Condition::new("six-eleven").is_err());
+        assert!(Condition::new("/(/").is_err());
+    }
+
+    #[test]
+    fn test_condition_matches() {
+        let pattern = Condition::new("/#[0-9a-f]{6}/").unwrap();
+        let one_of = Condition::new("{amb, blu}").unwrap();
+
+        assert!(Condition::AtLeast(18).matches(&FieldValue::Number(18)));
+        assert!(!Condition::AtMost(65).matches(&FieldValue::Number(66)));
+        assert!(!Condition::Exact(7).matches(&FieldValue::Text(String::from("7a"))));
+        assert!(pattern.matches(&FieldValue::new("#123abc")));
+        assert!(!pattern.matches(&FieldValue::new("#123abcd")));
+        assert!(one_of.matches(&FieldValue::new("blu")));
+        assert!(!one_of.matches(&FieldValue::new("grn")));
+    }
+
+    #[test]
+    fn test_rule_new() {
+        let result = Rule::new("1-3 or 5 or >=100 except 2 or 150-160").unwrap();
+
+        let expected = Rule {
+            alternatives: vec![
+                Condition::Range(1..=3),
+                Condition::Exact(5),
+                Condition::AtLeast(100),
+            ],
+            exclusions: vec![Condition::Exact(2), Condition::Range(150..=160)],
+        };
+
+        assert_eq!(result, expected);
+        assert!(Rule::new("").is_err());
+    }
+
+    #[test]
+    fn test_rule_matches() {
+        let rule = Rule::new("1-3 or 5 or >=100 except 2 or 150-160").unwrap();
+
+        assert!(rule.matches(&FieldValue::Number(1)));
+        assert!(!rule.matches(&FieldValue::Number(2)));
+        assert!(!rule.matches(&FieldValue::Number(4)));
+        assert!(rule.matches(&FieldValue::Number(5)));
+        assert!(rule.matches(&FieldValue::Number(1000)));
+        assert!(!rule.matches(&FieldValue::Number(155)));
+    }
+}
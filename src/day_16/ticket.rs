@@ -1,109 +1,65 @@
 use std::collections::{HashMap, HashSet};
-use std::ops::RangeInclusive;
+
+use super::report::{ColumnReport, InvalidValue, MappingIssue, ScannerReport, TicketReport};
+use super::rule::{FieldValue, Rule};
 
 #[derive(Debug, PartialEq)]
 struct TicketRules {
-    rule_names_and_ranges: HashMap<String, (RangeInclusive<usize>, RangeInclusive<usize>)>,
+    rules: HashMap<String, Rule>,
 }
 
 impl TicketRules {
     fn new() -> TicketRules {
         TicketRules {
-            rule_names_and_ranges: HashMap::new(),
+            rules: HashMap::new(),
         }
     }
 
-    fn add_rule(&mut self, info: &str) {
-        let split_info: Vec<&str> = info.split(": ").collect();
-
-        let field_name = split_info.get(0).expect(info).to_string();
+    fn add_rule(&mut self, info: &str) -> Result<(), String> {
+        let (field_name, rule_info) = info
+            .split_once(": ")
+            .ok_or_else(|| format!("Rule Missing Field Name: {:?}", info))?;
 
-        let all_range_info_split: Vec<&str> =
-            split_info.get(1).expect(info).split(" or ").collect();
+        let rule = Rule::new(rule_info)?;
 
-        let first_range_info: Vec<usize> = all_range_info_split
-            .get(0)
-            .expect(info)
-            .split('-')
-            .map(|s| s.parse().expect(info))
-            .collect();
+        self.rules.insert(String::from(field_name), rule);
 
-        let first_range =
-            *first_range_info.get(0).expect(info)..=*first_range_info.get(1).expect(info);
-
-        let second_range_info: Vec<usize> = all_range_info_split
-            .get(1)
-            .expect(info)
-            .split('-')
-            .map(|s| s.parse().expect(info))
-            .collect();
-
-        let second_range =
-            *second_range_info.get(0).expect(info)..=*second_range_info.get(1).expect(info);
-
-        self.rule_names_and_ranges
-            .insert(field_name, (first_range, second_range));
+        Ok(())
     }
 
-    fn follows_rules(&self, ticket: &Ticket) -> (bool, Vec<usize>) {
-        let mut follows_rules = true;
-        let mut invalid_values = Vec::new();
-
-        for field_value in &ticket.fields {
-            if !self.field_value_is_in_any_range(field_value) {
-                follows_rules = false;
-                invalid_values.push(*field_value);
-            }
-        }
-
-        (follows_rules, invalid_values)
+    fn invalid_values(&self, ticket: &Ticket) -> Vec<InvalidValue> {
+        ticket
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field_value)| !self.field_value_follows_any_rule(field_value))
+            .map(|(column, value)| InvalidValue {
+                column,
+                value: value.clone(),
+            })
+            .collect()
     }
 
-    fn field_value_is_in_any_range(&self, field_value: &usize) -> bool {
-        let mut field_is_is_any = false;
-
-        for (field_value_range_1, field_value_range_2) in self.rule_names_and_ranges.values() {
-            let field_value_in_ranges = field_value_range_1.contains(field_value)
-                || field_value_range_2.contains(field_value);
-
-            if field_value_in_ranges {
-                field_is_is_any = true;
-
-                break;
-            }
-        }
-
-        field_is_is_any
+    fn field_value_follows_any_rule(&self, field_value: &FieldValue) -> bool {
+        self.rules.values().any(|rule| rule.matches(field_value))
     }
 
-    fn fields_that_do_not_match(&self, field_value: usize) -> HashSet<String> {
-        let mut fields_that_do_not_match = HashSet::new();
-
-        for (field_name, (field_value_range_1, field_value_range_2)) in
-            self.rule_names_and_ranges.iter()
-        {
-            let field_value_in_ranges = field_value_range_1.contains(&field_value)
-                || field_value_range_2.contains(&field_value);
-
-            if !field_value_in_ranges {
-                fields_that_do_not_match.insert(field_name.clone());
-            }
-        }
-
-        fields_that_do_not_match
+    fn fields_that_do_not_match(&self, field_value: &FieldValue) -> HashSet<String> {
+        self.rules
+            .iter()
+            .filter(|(_, rule)| !rule.matches(field_value))
+            .map(|(field_name, _)| field_name.clone())
+            .collect()
     }
 
     fn get_field_names(&self) -> HashSet<String> {
-        self.rule_names_and_ranges
-            .keys()
-            .map(|s| s.clone())
-            .collect()
+        self.rules.keys().cloned().collect()
     }
 }
 
 #[derive(Debug, PartialEq)]
 struct Ticket {
-    fields: Vec<usize>,
+    fields: Vec<FieldValue>,
 }
 
 impl Ticket {
@@ -112,7 +68,7 @@ impl Ticket {
             Ticket { fields: Vec::new() }
         } else {
             Ticket {
-                fields: info.split(',').map(|s| s.parse().expect(info)).collect(),
+                fields: info.split(',').map(FieldValue::new).collect(),
             }
         }
     }
@@ -153,7 +109,7 @@ impl TicketScanner {
             }
 
             if in_rules_section {
-                rules.add_rule(line);
+                rules.add_rule(line).expect(line);
             } else if in_your_ticket_section {
                 your_ticket = Ticket::new(line);
             } else if in_nearby_tickets_section {
@@ -169,67 +125,160 @@ impl TicketScanner {
     }
 
     pub fn get_error_rate(&self) -> usize {
-        self.nearby_tickets
-            .iter()
-            .flat_map(|ticket| self.rules.follows_rules(ticket).1)
-            .sum()
+        self.get_report().error_rate()
+    }
+
+    pub fn get_your_ticket_fields(&self) -> HashMap<String, FieldValue> {
+        self.get_report()
+            .field_mapping()
+            .into_iter()
+            .filter_map(|(field_name, column)| {
+                self.your_ticket
+                    .fields
+                    .get(column)
+                    .map(|value| (field_name, value.clone()))
+            })
+            .collect()
     }
 
-    pub fn get_your_ticket_fields(&self) -> HashMap<String, usize> {
-        let mut possible_fields: Vec<HashSet<String>> = self
+    pub fn get_report(&self) -> ScannerReport {
+        let tickets: Vec<TicketReport> = self
+            .nearby_tickets
+            .iter()
+            .enumerate()
+            .map(|(ticket_index, ticket)| TicketReport {
+                ticket_index,
+                invalid_values: self.rules.invalid_values(ticket),
+            })
+            .collect();
+
+        let mut candidates: Vec<HashSet<String>> = self
             .your_ticket
             .fields
             .iter()
             .map(|_| self.rules.get_field_names())
             .collect();
 
-        for ticket in self.nearby_tickets.iter() {
-            if !self.rules.follows_rules(ticket).0 {
-                continue;
+        let valid_tickets = self
+            .nearby_tickets
+            .iter()
+            .zip(tickets.iter())
+            .filter(|(_, report)| report.is_valid())
+            .map(|(ticket, _)| ticket);
+
+        for ticket in valid_tickets {
+            for (candidates_for_column, field_value) in candidates.iter_mut().zip(&ticket.fields) {
+                for field_name in self.rules.fields_that_do_not_match(field_value).iter() {
+                    candidates_for_column.remove(field_name);
+                }
             }
+        }
+
+        let (assignments, issues) = self.assign_fields(&candidates);
+
+        let columns = candidates
+            .into_iter()
+            .zip(assignments)
+            .enumerate()
+            .map(|(column, (candidates_for_column, assigned_field))| {
+                let mut candidate_fields: Vec<String> = candidates_for_column.into_iter().collect();
 
-            for (index, field_value) in ticket.fields.iter().enumerate() {
-                let possible_fields_for_index = possible_fields.get_mut(index).unwrap();
+                candidate_fields.sort();
 
-                for field_name in self.rules.fields_that_do_not_match(*field_value).iter() {
-                    possible_fields_for_index.remove(field_name);
+                ColumnReport {
+                    column,
+                    candidate_fields,
+                    assigned_field,
                 }
+            })
+            .collect();
+
+        ScannerReport {
+            tickets,
+            columns,
+            issues,
+        }
+    }
+
+    /// Repeatedly pins columns that have exactly one candidate left, then explains whatever
+    /// could not be pinned.
+    fn assign_fields(
+        &self,
+        candidates: &[HashSet<String>],
+    ) -> (Vec<Option<String>>, Vec<MappingIssue>) {
+        let mut issues = Vec::new();
+        let mut remaining = candidates.to_vec();
+        let mut assignments: Vec<Option<String>> = vec![None; candidates.len()];
+
+        for (column, candidates_for_column) in candidates.iter().enumerate() {
+            if candidates_for_column.is_empty() {
+                issues.push(MappingIssue::NoCandidates { column });
             }
         }
 
-        let mut possible_fields_clone = possible_fields.clone();
+        while let Some(column) = (0..remaining.len())
+            .find(|&column| assignments[column].is_none() && remaining[column].len() == 1)
+        {
+            let field = remaining[column].iter().next().unwrap().clone();
+
+            assignments[column] = Some(field.clone());
 
-        possible_fields_clone.sort_by(|a, b| a.len().partial_cmp(&b.len()).unwrap());
+            let mut conflicting_columns = vec![column];
 
-        for possible_fields_for_index in possible_fields_clone {
-            for fields in possible_fields.iter_mut() {
-                if fields.len() == 1 {
+            for (other_column, other_candidates) in remaining.iter_mut().enumerate() {
+                if assignments[other_column].is_some() || !other_candidates.remove(&field) {
                     continue;
                 }
 
-                for field in possible_fields_for_index.iter() {
-                    fields.remove(field);
+                if other_candidates.is_empty() {
+                    conflicting_columns.push(other_column);
                 }
             }
+
+            if 1 < conflicting_columns.len() {
+                issues.push(MappingIssue::Conflict {
+                    field,
+                    columns: conflicting_columns,
+                });
+            }
         }
 
-        let mut result = HashMap::new();
+        let unresolved_columns: Vec<usize> = (0..remaining.len())
+            .filter(|&column| assignments[column].is_none() && 1 < remaining[column].len())
+            .collect();
 
-        for (index, possible_field) in possible_fields.iter().enumerate() {
-            let only_possible_field = possible_field
+        if !unresolved_columns.is_empty() {
+            let mut fields: Vec<String> = unresolved_columns
                 .iter()
-                .map(|s| s.clone())
-                .collect::<Vec<String>>()
-                .get(0)
-                .unwrap()
-                .clone();
+                .flat_map(|&column| remaining[column].iter().cloned())
+                .collect::<HashSet<String>>()
+                .into_iter()
+                .collect();
 
-            let value = self.your_ticket.fields.get(index).unwrap();
+            fields.sort();
 
-            result.insert(only_possible_field, *value);
+            issues.push(MappingIssue::Ambiguous {
+                columns: unresolved_columns,
+                fields,
+            });
         }
 
-        result
+        let mut unmatched_fields: Vec<String> = self
+            .rules
+            .get_field_names()
+            .into_iter()
+            .filter(|field| !candidates.iter().any(|column| column.contains(field)))
+            .collect();
+
+        unmatched_fields.sort();
+
+        issues.extend(
+            unmatched_fields
+                .into_iter()
+                .map(|field| MappingIssue::UnmatchedField { field }),
+        );
+
+        (assignments, issues)
     }
 }
 
@@ -270,35 +319,84 @@ mod tests {
         "14,5,9",
     ];
 
+    const TEST_AMBIGUOUS_SCANNER_DATA: [&str; 10] = [
+        "class: 0-10 or 20-30",
+        "row: 0-10 or 20-30",
+        "seat: 40-50 or 60-70",
+        "",
+        "your ticket:",
+        "1,2,45",
+        "",
+        "nearby tickets:",
+        "3,4,46",
+        "5,6,100",
+    ];
+
+    const TEST_TEXT_SCANNER_DATA: [&str; 10] = [
+        "age: >=18 except 100-200",
+        "eye color: {amb, blu, brn}",
+        "code: /[a-z]{2}[0-9]{2}/",
+        "",
+        "your ticket:",
+        "ab12,30,blu",
+        "",
+        "nearby tickets:",
+        "zz99,45,amb",
+        "xy00,7,grn",
+    ];
+
+    const TEST_CONFLICT_SCANNER_DATA: [&str; 8] = [
+        "class: 0-1 or 4-19",
+        "row: 20-30 or 40-50",
+        "",
+        "your ticket:",
+        "1,4",
+        "",
+        "nearby tickets:",
+        "5,6",
+    ];
+
     #[test]
     fn test_ticket_rules_add_rules() {
         let mut rules = TicketRules::new();
 
-        rules.add_rule(TEST_RULE_DATA);
+        rules.add_rule(TEST_RULE_DATA).unwrap();
 
         let expected = TicketRules {
-            rule_names_and_ranges: vec![(String::from("row test"), ((6..=11), (33..=44)))]
-                .into_iter()
-                .collect(),
+            rules: vec![(
+                String::from("row test"),
+                Rule::new("6-11 or 33-44").unwrap(),
+            )]
+            .into_iter()
+            .collect(),
         };
 
         assert_eq!(rules, expected);
     }
 
     #[test]
-    fn test_ticket_rules_follows_rules() {
+    fn test_ticket_rules_invalid_values() {
         let good_ticket = Ticket::new(TEST_GOOD_TICKET_DATA);
         let bad_ticket = Ticket::new(TEST_BAD_TICKET_DATA);
 
         let mut rules = TicketRules::new();
 
-        rules.add_rule(TEST_RULE_DATA);
+        rules.add_rule(TEST_RULE_DATA).unwrap();
 
-        let result_1 = rules.follows_rules(&good_ticket);
-        let result_2 = rules.follows_rules(&bad_ticket);
+        let result_1 = rules.invalid_values(&good_ticket);
+        let result_2 = rules.invalid_values(&bad_ticket);
 
-        let expected_1 = (true, Vec::new());
-        let expected_2 = (false, vec![4, 50]);
+        let expected_1 = Vec::new();
+        let expected_2 = vec![
+            InvalidValue {
+                column: 1,
+                value: FieldValue::Number(4),
+            },
+            InvalidValue {
+                column: 2,
+                value: FieldValue::Number(50),
+            },
+        ];
 
         assert_eq!(result_1, expected_1);
         assert_eq!(result_2, expected_2);
@@ -309,7 +407,11 @@ mod tests {
         let result = Ticket::new(TEST_GOOD_TICKET_DATA);
 
         let expected = Ticket {
-            fields: vec![7, 11, 33],
+            fields: vec![
+                FieldValue::Number(7),
+                FieldValue::Number(11),
+                FieldValue::Number(33),
+            ],
         };
 
         assert_eq!(result, expected);
@@ -323,29 +425,52 @@ mod tests {
 
         let expected = TicketScanner {
             rules: TicketRules {
-                rule_names_and_ranges: vec![
-                    (String::from("class"), ((1..=3), (5..=7))),
-                    (String::from("row"), ((6..=11), (33..=44))),
-                    (String::from("your seat"), ((13..=40), (45..=50))),
+                rules: vec![
+                    (String::from("class"), Rule::new("1-3 or 5-7").unwrap()),
+                    (String::from("row"), Rule::new("6-11 or 33-44").unwrap()),
+                    (
+                        String::from("your seat"),
+                        Rule::new("13-40 or 45-50").unwrap(),
+                    ),
                 ]
                 .into_iter()
                 .collect(),
             },
             your_ticket: Ticket {
-                fields: vec![7, 1, 14],
+                fields: vec![
+                    FieldValue::Number(7),
+                    FieldValue::Number(1),
+                    FieldValue::Number(14),
+                ],
             },
             nearby_tickets: vec![
                 Ticket {
-                    fields: vec![7, 3, 47],
+                    fields: vec![
+                        FieldValue::Number(7),
+                        FieldValue::Number(3),
+                        FieldValue::Number(47),
+                    ],
                 },
                 Ticket {
-                    fields: vec![40, 4, 50],
+                    fields: vec![
+                        FieldValue::Number(40),
+                        FieldValue::Number(4),
+                        FieldValue::Number(50),
+                    ],
                 },
                 Ticket {
-                    fields: vec![55, 2, 20],
+                    fields: vec![
+                        FieldValue::Number(55),
+                        FieldValue::Number(2),
+                        FieldValue::Number(20),
+                    ],
                 },
                 Ticket {
-                    fields: vec![38, 6, 12],
+                    fields: vec![
+                        FieldValue::Number(38),
+                        FieldValue::Number(6),
+                        FieldValue::Number(12),
+                    ],
                 },
             ],
         };
@@ -375,13 +500,121 @@ mod tests {
         let result = scanner.get_your_ticket_fields();
 
         let expected = vec![
-            (String::from("class"), 11),
-            (String::from("row"), 12),
-            (String::from("seat"), 13),
+            (String::from("class"), FieldValue::Number(11)),
+            (String::from("row"), FieldValue::Number(12)),
+            (String::from("seat"), FieldValue::Number(13)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_ticket_scanner_get_report() {
+        let input: Vec<String> = TEST_SCANNER_DATA_2.iter().map(|s| s.to_string()).collect();
+
+        let scanner = TicketScanner::new(&input);
+
+        let result = scanner.get_report();
+
+        let expected_columns = vec![
+            ColumnReport {
+                column: 0,
+                candidate_fields: vec![String::from("class"), String::from("row")],
+                assigned_field: Some(String::from("class")),
+            },
+            ColumnReport {
+                column: 1,
+                candidate_fields: vec![String::from("row")],
+                assigned_field: Some(String::from("row")),
+            },
+            ColumnReport {
+                column: 2,
+                candidate_fields: vec![
+                    String::from("class"),
+                    String::from("row"),
+                    String::from("seat"),
+                ],
+                assigned_field: Some(String::from("seat")),
+            },
+        ];
+
+        assert_eq!(result.tickets.len(), 3);
+        assert!(result.tickets.iter().all(|ticket| ticket.is_valid()));
+        assert_eq!(result.columns, expected_columns);
+        assert!(result.is_fully_mapped());
+    }
+
+    #[test]
+    fn test_ticket_scanner_get_report_ambiguous() {
+        let input: Vec<String> = TEST_AMBIGUOUS_SCANNER_DATA
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let scanner = TicketScanner::new(&input);
+
+        let result = scanner.get_report();
+
+        let expected_issues = vec![MappingIssue::Ambiguous {
+            columns: vec![0, 1],
+            fields: vec![String::from("class"), String::from("row")],
+        }];
+
+        assert_eq!(result.error_rate(), 100);
+        assert!(!result.tickets[1].is_valid());
+        assert_eq!(result.issues, expected_issues);
+        assert_eq!(result.columns[2].assigned_field, Some(String::from("seat")));
+    }
+
+    #[test]
+    fn test_ticket_scanner_get_report_conflict() {
+        let input: Vec<String> = TEST_CONFLICT_SCANNER_DATA
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let scanner = TicketScanner::new(&input);
+
+        let result = scanner.get_report();
+
+        let expected_issues = vec![
+            MappingIssue::Conflict {
+                field: String::from("class"),
+                columns: vec![0, 1],
+            },
+            MappingIssue::UnmatchedField {
+                field: String::from("row"),
+            },
+        ];
+
+        assert_eq!(result.issues, expected_issues);
+    }
+
+    #[test]
+    fn test_ticket_scanner_get_your_ticket_fields_with_text_rules() {
+        let input: Vec<String> = TEST_TEXT_SCANNER_DATA
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let scanner = TicketScanner::new(&input);
+
+        let result = scanner.get_your_ticket_fields();
+
+        let expected = vec![
+            (String::from("age"), FieldValue::Number(30)),
+            (
+                String::from("eye color"),
+                FieldValue::Text(String::from("blu")),
+            ),
+            (String::from("code"), FieldValue::Text(String::from("ab12"))),
         ]
         .into_iter()
         .collect();
 
+        assert_eq!(scanner.get_error_rate(), 7);
         assert_eq!(result, expected);
     }
 }
@@ -1,43 +1,67 @@
 mod passport;
+mod schema;
+mod validation;
 
 use crate::util::file_reader::to_string_vector;
+use crate::{get_user_input, wants_extras};
 
 use passport::Passport;
+use schema::PassportSchema;
+use validation::ValidationReport;
 
 pub fn run_day_4() {
     let file_input = to_string_vector("inputs/day_4.txt");
+    let schema_input = to_string_vector("inputs/day_4_schema.txt")
+        .and_then(|definition| PassportSchema::from_definition(&definition));
 
-    match file_input {
-        Ok(input_lines) => {
+    match (file_input, schema_input) {
+        (Ok(input_lines), Ok(schema)) => {
             let passports = get_passports(&input_lines);
 
             let part_1 = passports
                 .iter()
-                .filter(|passport| passport.contains_required_fields())
+                .filter(|passport| passport.contains_required_fields(&schema))
                 .count();
 
             println!("Day 4 Part 1: {:?}", part_1);
 
-            let part_2 = get_number_of_valid_passports(&passports);
+            let part_2 = get_number_of_valid_passports(&passports, &schema);
 
             println!("Day 4 Part 2: {:?}", part_2);
+
+            if !wants_extras() {
+                return;
+            }
+
+            print!("Print the validation report (0 = no, 1 = text, 2 = json): ");
+
+            let report = ValidationReport::new(&passports, &schema);
+
+            match get_user_input() {
+                1 => println!("{}", report),
+                2 => println!("{}", report.to_json()),
+                _ => {}
+            }
         }
-        Err(error) => println!("Error parsing file: {:?}", error),
+        (Err(error), _) | (_, Err(error)) => println!("Error parsing file: {:?}", error),
     };
 }
 
 fn get_passports(input_lines: &[String]) -> Vec<Passport> {
     input_lines
-        .split(|line| line.is_empty())
+        .split(|line| line.trim().is_empty())
+        .filter(|chunk| !chunk.is_empty())
         .map(|chunk| chunk.join(" "))
         .map(|passport_info| Passport::new(&passport_info))
         .collect()
 }
 
-fn get_number_of_valid_passports(passports: &[Passport]) -> usize {
+fn get_number_of_valid_passports(passports: &[Passport], schema: &PassportSchema) -> usize {
     passports
         .iter()
-        .filter(|passport| passport.contains_required_fields() && passport.contains_valid_info())
+        .filter(|passport| {
+            passport.contains_required_fields(schema) && passport.contains_valid_info(schema)
+        })
         .count()
 }
 
@@ -61,8 +85,28 @@ mod tests {
 
         let passports = get_passports(&file_input);
 
-        let result = get_number_of_valid_passports(&passports);
+        let result = get_number_of_valid_passports(&passports, &PassportSchema::default());
 
         assert_eq!(result, 2);
     }
+
+    #[test]
+    fn test_get_passports_with_crlf_and_tabs() {
+        let input_lines = vec![
+            String::from("ecl:gry\tpid:860033327\r"),
+            String::from("eyr:2020  hcl:#fffffd\r"),
+            String::from("\r"),
+            String::from(" \t"),
+            String::from("iyr:2013 ecl:amb\r"),
+        ];
+
+        let result = get_passports(&input_lines);
+
+        let expected = vec![
+            Passport::new("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd"),
+            Passport::new("iyr:2013 ecl:amb"),
+        ];
+
+        assert_eq!(result, expected);
+    }
 }
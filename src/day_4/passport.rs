@@ -1,133 +1,131 @@
-use std::collections::HashSet;
+use super::schema::PassportSchema;
+use super::validation::{InvalidField, PassportValidation};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-enum PassportInfo {
-    BirthYear(u32),
-    IssueYear(u32),
-    ExpirationYear(u32),
-    Height(String),
-    HairColor(String),
-    EyeColor(String),
-    PassportId(String),
-    CountryId(u32),
+struct PassportInfo {
+    key: String,
+    value: String,
 }
 
 impl PassportInfo {
+    /// Splits a `key:value` token; a token without a `:` is kept as a key with an empty value.
     fn new(single_info: &str) -> PassportInfo {
-        let info_type = &single_info[0..3];
-        let info_value = &single_info[4..];
-
-        let error_info = format!("Error for Info: {:?}", single_info);
-
-        match info_type {
-            "byr" => PassportInfo::BirthYear(info_value.parse().expect(error_info.as_str())),
-            "iyr" => PassportInfo::IssueYear(info_value.parse().expect(error_info.as_str())),
-            "eyr" => PassportInfo::ExpirationYear(info_value.parse().expect(error_info.as_str())),
-            "hgt" => PassportInfo::Height(String::from(info_value)),
-            "hcl" => PassportInfo::HairColor(String::from(info_value)),
-            "ecl" => PassportInfo::EyeColor(String::from(info_value)),
-            "pid" => PassportInfo::PassportId(String::from(info_value)),
-            "cid" => PassportInfo::CountryId(info_value.parse().expect(error_info.as_str())),
-            _ => panic!("Unknown PassportInfo Type: {:?}", info_type),
+        let (info_type, info_value) = single_info.split_once(':').unwrap_or((single_info, ""));
+
+        PassportInfo {
+            key: String::from(info_type),
+            value: String::from(info_value),
         }
     }
 
-    fn is_valid(&self) -> bool {
-        match self {
-            PassportInfo::BirthYear(year) => (1920..2003).contains(year),
-            PassportInfo::IssueYear(year) => (2010..2021).contains(year),
-            PassportInfo::ExpirationYear(year) => (2020..2031).contains(year),
-            PassportInfo::Height(info) => {
-                if info.contains("cm") {
-                    let value: u32 = info.replace("cm", "").parse().unwrap_or(0);
-
-                    (150..194).contains(&value)
-                } else if info.contains("in") {
-                    let value: u32 = info.replace("in", "").parse().unwrap_or(0);
-
-                    (59..77).contains(&value)
-                } else {
-                    false
-                }
-            }
-            PassportInfo::HairColor(info) => {
-                let size_valid = info.len() == 7;
-                let valid_start = info.starts_with('#');
-                let valid_rest = info[1..].chars().all(|c| c.is_digit(16));
-
-                size_valid && valid_start && valid_rest
-            }
-            PassportInfo::EyeColor(info) => {
-                info == "amb"
-                    || info == "blu"
-                    || info == "brn"
-                    || info == "gry"
-                    || info == "grn"
-                    || info == "hzl"
-                    || info == "oth"
-            }
-            PassportInfo::PassportId(info) => {
-                let size_valid = info.len() == 9;
-                let characters_valid = info.chars().all(|c| c.is_digit(10));
-
-                size_valid && characters_valid
-            }
-            PassportInfo::CountryId(_) => true,
+    fn is_valid(&self, schema: &PassportSchema) -> bool {
+        match schema.get_field(&self.key) {
+            Some(field) => field.is_valid(&self.value),
+            None => true,
         }
     }
 }
 
+/// The raw `key:value` pairs of a passport, in input order and including any repeats.
 #[derive(Debug, PartialEq)]
 pub struct Passport {
-    info: HashSet<PassportInfo>,
+    info: Vec<PassportInfo>,
 }
 
 impl Passport {
     pub fn new(all_info: &str) -> Passport {
         Passport {
-            info: all_info
-                .split(' ')
-                .map(|split_value| PassportInfo::new(split_value))
-                .collect(),
+            info: all_info.split_whitespace().map(PassportInfo::new).collect(),
         }
     }
 
-    pub fn contains_required_fields(&self) -> bool {
-        let mut has_birth_year = false;
-        let mut has_issue_year = false;
-        let mut has_expiration_year = false;
-        let mut has_height = false;
-        let mut has_hair_color = false;
-        let mut has_eye_color = false;
-        let mut has_passport_id = false;
+    pub fn get_values(&self, key: &str) -> Vec<&str> {
+        self.info
+            .iter()
+            .filter(|passport_info| passport_info.key == key)
+            .map(|passport_info| passport_info.value.as_str())
+            .collect()
+    }
+
+    pub fn duplicate_keys(&self) -> Vec<&str> {
+        let mut duplicate_keys = Vec::new();
+
+        for (index, passport_info) in self.info.iter().enumerate() {
+            let key = passport_info.key.as_str();
+
+            let seen_before = self.info[..index].iter().any(|other| other.key == key);
+
+            if seen_before && !duplicate_keys.contains(&key) {
+                duplicate_keys.push(key);
+            }
+        }
+
+        duplicate_keys
+    }
+
+    pub fn unknown_keys(&self, schema: &PassportSchema) -> Vec<&str> {
+        let mut unknown_keys = Vec::new();
 
-        // PassportInfo::CountryId is optional.
         for passport_info in &self.info {
-            match passport_info {
-                PassportInfo::BirthYear(_) => has_birth_year = true,
-                PassportInfo::IssueYear(_) => has_issue_year = true,
-                PassportInfo::ExpirationYear(_) => has_expiration_year = true,
-                PassportInfo::Height(_) => has_height = true,
-                PassportInfo::HairColor(_) => has_hair_color = true,
-                PassportInfo::EyeColor(_) => has_eye_color = true,
-                PassportInfo::PassportId(_) => has_passport_id = true,
-                PassportInfo::CountryId(_) => {}
-            };
+            let key = passport_info.key.as_str();
+
+            if schema.get_field(key).is_none() && !unknown_keys.contains(&key) {
+                unknown_keys.push(key);
+            }
         }
 
-        has_birth_year
-            && has_issue_year
-            && has_expiration_year
-            && has_height
-            && has_hair_color
-            && has_eye_color
-            && has_passport_id
+        unknown_keys
+    }
+
+    pub fn contains_required_fields(&self, schema: &PassportSchema) -> bool {
+        schema
+            .required_fields()
+            .all(|field| !self.get_values(&field.name).is_empty())
     }
 
-    pub fn contains_valid_info(&self) -> bool {
+    /// Checks every field the schema knows about; fields outside the schema are ignored.
+    pub fn contains_valid_info(&self, schema: &PassportSchema) -> bool {
         self.info
             .iter()
-            .all(|passport_info| passport_info.is_valid())
+            .all(|passport_info| passport_info.is_valid(schema))
+    }
+
+    pub fn validate(&self, schema: &PassportSchema) -> PassportValidation {
+        let missing_fields = schema
+            .required_fields()
+            .filter(|field| self.get_values(&field.name).is_empty())
+            .map(|field| field.name.clone())
+            .collect();
+
+        let invalid_fields = self
+            .info
+            .iter()
+            .filter_map(|passport_info| {
+                schema
+                    .get_field(&passport_info.key)?
+                    .failed_validator(&passport_info.value)
+                    .map(|validator| InvalidField {
+                        key: passport_info.key.clone(),
+                        value: passport_info.value.clone(),
+                        rule: validator.to_string(),
+                    })
+            })
+            .collect();
+
+        PassportValidation {
+            missing_fields,
+            invalid_fields,
+            duplicate_keys: self
+                .duplicate_keys()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            unknown_keys: self
+                .unknown_keys(schema)
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
     }
 }
 
@@ -137,25 +135,7 @@ mod tests {
 
     const EYE_COLOR: &str = "ecl:gry";
     const BIRTH_YEAR: &str = "byr:1937";
-
-    const VALID_HEIGHT_1: &str = "150cm";
-    const VALID_HEIGHT_2: &str = "76in";
-    const INVALID_HEIGHT_1: &str = "179";
-    const INVALID_HEIGHT_2: &str = "in";
-
-    const VALID_BIRTH_YEAR: u32 = 1920;
-    const INVALID_BIRTH_YEAR: u32 = 2003;
-
-    const VALID_HAIR_COLOR: &str = "#123abc";
-    const INVALID_HAIR_COLOR_1: &str = "#123abz";
-    const INVALID_HAIR_COLOR_2: &str = "123abc";
-
-    const VALID_EYE_COLOR: &str = "gry";
-    const INVALID_EYE_COLOR: &str = "wat";
-
-    const VALID_PASSPORT_ID: &str = "000000001";
-    const INVALID_PASSPORT_ID_1: &str = "#00000001";
-    const INVALID_PASSPORT_ID_2: &str = "0000000001";
+    const UNKNOWN_INFO: &str = "abc:xyz";
 
     const VALID_PASSPORT_INFO: &str =
         "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm";
@@ -170,52 +150,35 @@ mod tests {
     fn test_passport_info_new() {
         let result_1 = PassportInfo::new(EYE_COLOR);
         let result_2 = PassportInfo::new(BIRTH_YEAR);
-        let expected_1 = PassportInfo::EyeColor(String::from("gry"));
-        let expected_2 = PassportInfo::BirthYear(1937);
+        let result_3 = PassportInfo::new(UNKNOWN_INFO);
+        let expected_1 = PassportInfo {
+            key: String::from("ecl"),
+            value: String::from("gry"),
+        };
+        let expected_2 = PassportInfo {
+            key: String::from("byr"),
+            value: String::from("1937"),
+        };
+        let expected_3 = PassportInfo {
+            key: String::from("abc"),
+            value: String::from("xyz"),
+        };
 
         assert_eq!(result_1, expected_1);
         assert_eq!(result_2, expected_2);
+        assert_eq!(result_3, expected_3);
+        assert_eq!(PassportInfo::new("hg").value, "");
+        assert_eq!(PassportInfo::new("hcl:#a:b").value, "#a:b");
     }
 
     #[test]
     fn test_passport_info_is_valid() {
-        let valid_height_1 = PassportInfo::Height(String::from(VALID_HEIGHT_1));
-        let valid_height_2 = PassportInfo::Height(String::from(VALID_HEIGHT_2));
-        let invalid_height_1 = PassportInfo::Height(String::from(INVALID_HEIGHT_1));
-        let invalid_height_2 = PassportInfo::Height(String::from(INVALID_HEIGHT_2));
-
-        let valid_birth_year = PassportInfo::BirthYear(VALID_BIRTH_YEAR);
-        let invalid_birth_year = PassportInfo::BirthYear(INVALID_BIRTH_YEAR);
-
-        let valid_hair_color = PassportInfo::HairColor(String::from(VALID_HAIR_COLOR));
-        let invalid_hair_color_1 = PassportInfo::HairColor(String::from(INVALID_HAIR_COLOR_1));
-        let invalid_hair_color_2 = PassportInfo::HairColor(String::from(INVALID_HAIR_COLOR_2));
-
-        let valid_eye_color = PassportInfo::EyeColor(String::from(VALID_EYE_COLOR));
-        let invalid_eye_color = PassportInfo::EyeColor(String::from(INVALID_EYE_COLOR));
-
-        let valid_passport = PassportInfo::PassportId(String::from(VALID_PASSPORT_ID));
-        let invalid_passport_1 = PassportInfo::PassportId(String::from(INVALID_PASSPORT_ID_1));
-        let invalid_passport_2 = PassportInfo::PassportId(String::from(INVALID_PASSPORT_ID_2));
-
-        assert!(valid_height_1.is_valid());
-        assert!(valid_height_2.is_valid());
-        assert!(!invalid_height_1.is_valid());
-        assert!(!invalid_height_2.is_valid());
-
-        assert!(valid_birth_year.is_valid());
-        assert!(!invalid_birth_year.is_valid());
-
-        assert!(valid_hair_color.is_valid());
-        assert!(!invalid_hair_color_1.is_valid());
-        assert!(!invalid_hair_color_2.is_valid());
-
-        assert!(valid_eye_color.is_valid());
-        assert!(!invalid_eye_color.is_valid());
+        let schema = PassportSchema::default();
 
-        assert!(valid_passport.is_valid());
-        assert!(!invalid_passport_1.is_valid());
-        assert!(!invalid_passport_2.is_valid());
+        assert!(PassportInfo::new("hgt:150cm").is_valid(&schema));
+        assert!(!PassportInfo::new("hgt:179").is_valid(&schema));
+        assert!(!PassportInfo::new("byr:2003").is_valid(&schema));
+        assert!(PassportInfo::new(UNKNOWN_INFO).is_valid(&schema));
     }
 
     #[test]
@@ -223,19 +186,16 @@ mod tests {
         let result = Passport::new(VALID_PASSPORT_INFO);
 
         let expected = Passport {
-            info: [
-                PassportInfo::EyeColor(String::from("gry")),
-                PassportInfo::PassportId(String::from("860033327")),
-                PassportInfo::ExpirationYear(2020),
-                PassportInfo::HairColor(String::from("#fffffd")),
-                PassportInfo::BirthYear(1937),
-                PassportInfo::IssueYear(2017),
-                PassportInfo::CountryId(147),
-                PassportInfo::Height(String::from("183cm")),
-            ]
-            .iter()
-            .cloned()
-            .collect(),
+            info: vec![
+                PassportInfo::new("ecl:gry"),
+                PassportInfo::new("pid:860033327"),
+                PassportInfo::new("eyr:2020"),
+                PassportInfo::new("hcl:#fffffd"),
+                PassportInfo::new("byr:1937"),
+                PassportInfo::new("iyr:2017"),
+                PassportInfo::new("cid:147"),
+                PassportInfo::new("hgt:183cm"),
+            ],
         };
 
         assert_eq!(result, expected);
@@ -248,9 +208,38 @@ mod tests {
         let passport_3 = Passport::new(INVALID_PASSPORT_INFO);
         let passport_4 = Passport::new(INVALID_PASSPORT_INFO_2);
 
-        assert!(passport_1.contains_required_fields());
-        assert!(passport_2.contains_required_fields());
-        assert!(!passport_3.contains_required_fields());
-        assert!(!passport_4.contains_required_fields());
+        let schema = PassportSchema::default();
+
+        assert!(passport_1.contains_required_fields(&schema));
+        assert!(passport_2.contains_required_fields(&schema));
+        assert!(!passport_3.contains_required_fields(&schema));
+        assert!(!passport_4.contains_required_fields(&schema));
+    }
+
+    #[test]
+    fn test_passport_new_with_irregular_whitespace() {
+        let result = Passport::new("ecl:gry\tpid:860033327   eyr:2020\r\n hcl:#fffffd\r");
+
+        let expected = Passport {
+            info: vec![
+                PassportInfo::new("ecl:gry"),
+                PassportInfo::new("pid:860033327"),
+                PassportInfo::new("eyr:2020"),
+                PassportInfo::new("hcl:#fffffd"),
+            ],
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_passport_duplicate_and_unknown_keys() {
+        let passport = Passport::new("pid:1 abc:x pid:2 byr:1937 abc:y pid:1 xyz");
+
+        let schema = PassportSchema::default();
+
+        assert_eq!(passport.get_values("pid"), vec!["1", "2", "1"]);
+        assert_eq!(passport.duplicate_keys(), vec!["pid", "abc"]);
+        assert_eq!(passport.unknown_keys(&schema), vec!["abc", "xyz"]);
     }
 }
@@ -0,0 +1,332 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub enum Validator {
+    Range(RangeInclusive<i64>),
+    Units(Vec<(String, RangeInclusive<i64>)>),
+    /// The pattern as written in the schema, and the regex matching whole values with it.
+    Pattern(String, Regex),
+    OneOf(Vec<String>),
+}
+
+impl Validator {
+    /// Parses a `kind = arguments` line from a schema definition.
+    ///
+    /// Supported kinds are `range = 1920-2002`, `units = 150-193 cm, 59-76 in`,
+    /// `pattern = #[0-9a-f]{6}` (matched against the whole value) and
+    /// `one_of = amb, blu, brn`.
+    pub fn new(kind: &str, arguments: &str) -> Result<Validator, String> {
+        match kind {
+            "range" => Ok(Validator::Range(Self::parse_range(arguments)?)),
+            "units" => arguments
+                .split(',')
+                .map(|unit_info| {
+                    let unit_info = unit_info.trim();
+
+                    let (range_info, unit) = unit_info
+                        .split_once(' ')
+                        .ok_or_else(|| format!("Unit Range Missing Unit: {:?}", unit_info))?;
+
+                    Ok((String::from(unit.trim()), Self::parse_range(range_info)?))
+                })
+                .collect::<Result<Vec<(String, RangeInclusive<i64>)>, String>>()
+                .map(Validator::Units),
+            "pattern" => Regex::new(&format!("^(?:{})$", arguments))
+                .map(|regex| Validator::Pattern(String::from(arguments), regex))
+                .map_err(|error| format!("Bad Pattern {:?}: {}", arguments, error)),
+            "one_of" => Ok(Validator::OneOf(
+                arguments
+                    .split(',')
+                    .map(|member| String::from(member.trim()))
+                    .filter(|member| !member.is_empty())
+                    .collect(),
+            )),
+            _ => Err(format!("Unknown Validator: {:?}", kind)),
+        }
+    }
+
+    pub fn is_valid(&self, value: &str) -> bool {
+        match self {
+            Validator::Range(range) => value
+                .parse()
+                .map(|number| range.contains(&number))
+                .unwrap_or(false),
+            Validator::Units(units_and_ranges) => units_and_ranges.iter().any(|(unit, range)| {
+                value
+                    .strip_suffix(unit.as_str())
+                    .and_then(|number_info| number_info.parse().ok())
+                    .map(|number| range.contains(&number))
+                    .unwrap_or(false)
+            }),
+            Validator::Pattern(_, regex) => regex.is_match(value),
+            Validator::OneOf(members) => members.iter().any(|member| member == value),
+        }
+    }
+
+    fn parse_range(range_info: &str) -> Result<RangeInclusive<i64>, String> {
+        let parse_error = |_| format!("Can't Parse Range: {:?}", range_info);
+
+        let (start, end) = range_info
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| format!("Range Missing '-': {:?}", range_info))?;
+
+        Ok(start.trim().parse().map_err(parse_error)?..=end.trim().parse().map_err(parse_error)?)
+    }
+}
+
+impl PartialEq for Validator {
+    fn eq(&self, other: &Validator) -> bool {
+        match (self, other) {
+            (Validator::Range(a), Validator::Range(b)) => a == b,
+            (Validator::Units(a), Validator::Units(b)) => a == b,
+            (Validator::Pattern(a, _), Validator::Pattern(b, _)) => a == b,
+            (Validator::OneOf(a), Validator::OneOf(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Validator::Range(range) => write!(f, "range = {}-{}", range.start(), range.end()),
+            Validator::Units(units_and_ranges) => {
+                let units_info: Vec<String> = units_and_ranges
+                    .iter()
+                    .map(|(unit, range)| format!("{}-{} {}", range.start(), range.end(), unit))
+                    .collect();
+
+                write!(f, "units = {}", units_info.join(", "))
+            }
+            Validator::Pattern(pattern, _) => write!(f, "pattern = {}", pattern),
+            Validator::OneOf(members) => write!(f, "one_of = {}", members.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FieldSpec {
+    pub name: String,
+    pub required: bool,
+    pub validators: Vec<Validator>,
+}
+
+impl FieldSpec {
+    pub fn new(name: &str, required: bool, validators: Vec<Validator>) -> FieldSpec {
+        FieldSpec {
+            name: String::from(name),
+            required,
+            validators,
+        }
+    }
+
+    pub fn is_valid(&self, value: &str) -> bool {
+        self.failed_validator(value).is_none()
+    }
+
+    pub fn failed_validator(&self, value: &str) -> Option<&Validator> {
+        self.validators
+            .iter()
+            .find(|validator| !validator.is_valid(value))
+    }
+}
+
+/// Describes which passport fields exist, which are required and how their values are checked.
+///
+/// A schema is either built in code with `with_required_field`/`with_optional_field`, or loaded
+/// from a definition with one `[field]` section per field followed by `required = true|false`
+/// and any number of validator lines (see `Validator::new`). Lines starting with `#` are comments.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PassportSchema {
+    fields: Vec<FieldSpec>,
+}
+
+impl PassportSchema {
+    pub fn new() -> PassportSchema {
+        PassportSchema { fields: Vec::new() }
+    }
+
+    pub fn from_definition(definition: &[String]) -> Result<PassportSchema, String> {
+        let mut schema = PassportSchema::new();
+
+        for line in definition.iter().map(|line| line.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                let name = line[1..line.len() - 1].trim();
+
+                schema = schema.with_required_field(name, Vec::new());
+
+                continue;
+            }
+
+            let field = schema
+                .fields
+                .last_mut()
+                .ok_or_else(|| format!("Line Outside Of A [field] Section: {:?}", line))?;
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Line Missing '=': {:?}", line))?;
+
+            match (key.trim(), value.trim()) {
+                ("required", "true") => field.required = true,
+                ("required", "false") => field.required = false,
+                ("required", other) => return Err(format!("Bad Required Value: {:?}", other)),
+                (kind, arguments) => field.validators.push(Validator::new(kind, arguments)?),
+            }
+        }
+
+        Ok(schema)
+    }
+
+    pub fn with_required_field(self, name: &str, validators: Vec<Validator>) -> PassportSchema {
+        self.with_field(FieldSpec::new(name, true, validators))
+    }
+
+    pub fn with_optional_field(self, name: &str, validators: Vec<Validator>) -> PassportSchema {
+        self.with_field(FieldSpec::new(name, false, validators))
+    }
+
+    pub fn get_field(&self, name: &str) -> Option<&FieldSpec> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    pub fn required_fields(&self) -> impl Iterator<Item = &FieldSpec> {
+        self.fields.iter().filter(|field| field.required)
+    }
+
+    fn with_field(mut self, field: FieldSpec) -> PassportSchema {
+        self.fields
+            .retain(|existing_field| existing_field.name != field.name);
+        self.fields.push(field);
+
+        self
+    }
+}
+
+impl Default for PassportSchema {
+    /// The North Pole passport rules from day 4.
+    fn default() -> PassportSchema {
+        let validator = |kind: &str, arguments: &str| Validator::new(kind, arguments).unwrap();
+
+        PassportSchema::new()
+            .with_required_field("byr", vec![validator("range", "1920-2002")])
+            .with_required_field("iyr", vec![validator("range", "2010-2020")])
+            .with_required_field("eyr", vec![validator("range", "2020-2030")])
+            .with_required_field("hgt", vec![validator("units", "150-193 cm, 59-76 in")])
+            .with_required_field("hcl", vec![validator("pattern", "#[0-9a-fA-F]{6}")])
+            .with_required_field(
+                "ecl",
+                vec![validator("one_of", "amb, blu, brn, gry, grn, hzl, oth")],
+            )
+            .with_required_field("pid", vec![validator("pattern", "[0-9]{9}")])
+            .with_optional_field("cid", Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::file_reader::to_string_vector;
+
+    #[test]
+    fn test_validator_new() {
+        let result_1 = Validator::new("range", "1920-2002");
+        let result_2 = Validator::new("units", "150-193 cm, 59-76 in");
+        let result_3 = Validator::new("one_of", "amb, blu");
+        let result_4 = Validator::new("length", "9");
+        let result_5 = Validator::new("units", "150-193");
+
+        let expected_1 = Ok(Validator::Range(1920..=2002));
+        let expected_2 = Ok(Validator::Units(vec![
+            (String::from("cm"), 150..=193),
+            (String::from("in"), 59..=76),
+        ]));
+        let expected_3 = Ok(Validator::OneOf(vec![
+            String::from("amb"),
+            String::from("blu"),
+        ]));
+
+        assert_eq!(result_1, expected_1);
+        assert_eq!(result_2, expected_2);
+        assert_eq!(result_3, expected_3);
+        assert!(result_4.is_err());
+        assert!(result_5.is_err());
+    }
+
+    #[test]
+    fn test_validator_display() {
+        let pattern = Validator::new("pattern", "[0-9]{9}").unwrap();
+        let units = Validator::new("units", "150-193 cm, 59-76 in").unwrap();
+
+        assert_eq!(pattern.to_string(), "pattern = [0-9]{9}");
+        assert_eq!(
+            Validator::new("pattern", "a|b").unwrap().to_string(),
+            "pattern = a|b"
+        );
+        assert_eq!(units.to_string(), "units = 150-193 cm, 59-76 in");
+    }
+
+    #[test]
+    fn test_default_schema_is_valid() {
+        let schema = PassportSchema::default();
+
+        let is_valid = |name: &str, value: &str| schema.get_field(name).unwrap().is_valid(value);
+
+        assert!(is_valid("hgt", "150cm"));
+        assert!(is_valid("hgt", "76in"));
+        assert!(!is_valid("hgt", "179"));
+        assert!(!is_valid("hgt", "in"));
+
+        assert!(is_valid("byr", "1920"));
+        assert!(!is_valid("byr", "2003"));
+
+        assert!(is_valid("hcl", "#123abc"));
+        assert!(!is_valid("hcl", "#123abz"));
+        assert!(!is_valid("hcl", "123abc"));
+
+        assert!(is_valid("ecl", "gry"));
+        assert!(!is_valid("ecl", "wat"));
+
+        assert!(is_valid("pid", "000000001"));
+        assert!(!is_valid("pid", "#00000001"));
+        assert!(!is_valid("pid", "0000000001"));
+
+        assert!(is_valid("cid", "anything"));
+    }
+
+    #[test]
+    fn test_passport_schema_from_definition() {
+        let definition = to_string_vector("test_inputs/day_4_schema.txt").unwrap();
+
+        let result = PassportSchema::from_definition(&definition);
+
+        assert_eq!(result, Ok(PassportSchema::default()));
+    }
+
+    #[test]
+    fn test_passport_schema_from_definition_errors() {
+        let no_section = vec![String::from("required = true")];
+        let bad_required = vec![String::from("[byr]"), String::from("required = maybe")];
+        let bad_validator = vec![String::from("[byr]"), String::from("range = old-new")];
+
+        assert!(PassportSchema::from_definition(&no_section).is_err());
+        assert!(PassportSchema::from_definition(&bad_required).is_err());
+        assert!(PassportSchema::from_definition(&bad_validator).is_err());
+    }
+
+    #[test]
+    fn test_passport_schema_with_field_replaces_existing() {
+        let schema = PassportSchema::default().with_optional_field("byr", Vec::new());
+
+        assert_eq!(schema.required_fields().count(), 6);
+        assert!(schema.get_field("byr").unwrap().is_valid("1800"));
+    }
+}
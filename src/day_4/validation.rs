@@ -0,0 +1,248 @@
+use std::fmt;
+
+use super::passport::Passport;
+use super::schema::PassportSchema;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvalidField {
+    pub key: String,
+    pub value: String,
+    pub rule: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct PassportValidation {
+    pub missing_fields: Vec<String>,
+    pub invalid_fields: Vec<InvalidField>,
+    pub duplicate_keys: Vec<String>,
+    pub unknown_keys: Vec<String>,
+}
+
+impl PassportValidation {
+    /// Duplicate and unknown keys are reported but don't make a passport invalid on their own.
+    pub fn is_valid(&self) -> bool {
+        self.missing_fields.is_empty() && self.invalid_fields.is_empty()
+    }
+
+    pub fn has_issues(&self) -> bool {
+        !self.is_valid() || !self.duplicate_keys.is_empty() || !self.unknown_keys.is_empty()
+    }
+
+    fn to_json(&self, index: usize) -> String {
+        let invalid_fields: Vec<String> = self
+            .invalid_fields
+            .iter()
+            .map(|invalid_field| {
+                format!(
+                    "{{\"key\":{},\"value\":{},\"rule\":{}}}",
+                    json_string(&invalid_field.key),
+                    json_string(&invalid_field.value),
+                    json_string(&invalid_field.rule)
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"index\":{},\"valid\":{},\"missing_fields\":{},\"invalid_fields\":[{}],\
+             \"duplicate_keys\":{},\"unknown_keys\":{}}}",
+            index,
+            self.is_valid(),
+            json_string_array(&self.missing_fields),
+            invalid_fields.join(","),
+            json_string_array(&self.duplicate_keys),
+            json_string_array(&self.unknown_keys)
+        )
+    }
+}
+
+impl fmt::Display for PassportValidation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut issues = Vec::new();
+
+        if !self.missing_fields.is_empty() {
+            issues.push(format!("missing {}", self.missing_fields.join(", ")));
+        }
+
+        for invalid_field in &self.invalid_fields {
+            issues.push(format!(
+                "invalid {} = {:?} ({})",
+                invalid_field.key, invalid_field.value, invalid_field.rule
+            ));
+        }
+
+        if !self.duplicate_keys.is_empty() {
+            issues.push(format!("duplicate {}", self.duplicate_keys.join(", ")));
+        }
+
+        if !self.unknown_keys.is_empty() {
+            issues.push(format!("unknown {}", self.unknown_keys.join(", ")));
+        }
+
+        if issues.is_empty() {
+            write!(f, "valid")
+        } else {
+            write!(f, "{}", issues.join("; "))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ValidationReport {
+    pub passports: Vec<PassportValidation>,
+}
+
+impl ValidationReport {
+    pub fn new(passports: &[Passport], schema: &PassportSchema) -> ValidationReport {
+        ValidationReport {
+            passports: passports
+                .iter()
+                .map(|passport| passport.validate(schema))
+                .collect(),
+        }
+    }
+
+    pub fn number_of_valid_passports(&self) -> usize {
+        self.passports
+            .iter()
+            .filter(|validation| validation.is_valid())
+            .count()
+    }
+
+    pub fn to_json(&self) -> String {
+        let passports: Vec<String> = self
+            .passports
+            .iter()
+            .enumerate()
+            .map(|(index, validation)| validation.to_json(index))
+            .collect();
+
+        format!(
+            "{{\"total\":{},\"valid\":{},\"passports\":[{}]}}",
+            self.passports.len(),
+            self.number_of_valid_passports(),
+            passports.join(",")
+        )
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let number_of_valid_passports = self.number_of_valid_passports();
+
+        write!(
+            f,
+            "Passports: {} total, {} valid, {} invalid",
+            self.passports.len(),
+            number_of_valid_passports,
+            self.passports.len() - number_of_valid_passports
+        )?;
+
+        for (index, validation) in self.passports.iter().enumerate() {
+            if validation.has_issues() {
+                write!(f, "\nPassport {}: {}", index, validation)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+
+    result
+}
+
+fn json_string_array(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|value| json_string(value)).collect();
+
+    format!("[{}]", values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_PASSPORT_INFO: &str =
+        "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm";
+    const INVALID_PASSPORT_INFO: &str =
+        "hcl:#cfa07d eyr:2025 pid:166559648 pid:#1bb4d8 iyr:2011 ecl:brn hgt:59in abc:xyz";
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+    }
+
+    #[test]
+    fn test_validation_report_new() {
+        let passports = vec![
+            Passport::new(VALID_PASSPORT_INFO),
+            Passport::new(INVALID_PASSPORT_INFO),
+        ];
+
+        let result = ValidationReport::new(&passports, &PassportSchema::default());
+
+        let expected = ValidationReport {
+            passports: vec![
+                PassportValidation::default(),
+                PassportValidation {
+                    missing_fields: vec![String::from("byr")],
+                    invalid_fields: vec![InvalidField {
+                        key: String::from("pid"),
+                        value: String::from("#1bb4d8"),
+                        rule: String::from("pattern = [0-9]{9}"),
+                    }],
+                    duplicate_keys: vec![String::from("pid")],
+                    unknown_keys: vec![String::from("abc")],
+                },
+            ],
+        };
+
+        assert_eq!(result, expected);
+        assert_eq!(result.number_of_valid_passports(), 1);
+    }
+
+    #[test]
+    fn test_validation_report_display() {
+        let passports = vec![
+            Passport::new(VALID_PASSPORT_INFO),
+            Passport::new(INVALID_PASSPORT_INFO),
+        ];
+
+        let report = ValidationReport::new(&passports, &PassportSchema::default());
+
+        let expected = "Passports: 2 total, 1 valid, 1 invalid\n\
+                        Passport 1: missing byr; \
+                        invalid pid = \"#1bb4d8\" (pattern = [0-9]{9}); \
+                        duplicate pid; unknown abc";
+
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[test]
+    fn test_validation_report_to_json() {
+        let passports = vec![Passport::new(VALID_PASSPORT_INFO)];
+
+        let report = ValidationReport::new(&passports, &PassportSchema::default());
+
+        let expected = "{\"total\":1,\"valid\":1,\"passports\":[{\"index\":0,\"valid\":true,\
+                        \"missing_fields\":[],\"invalid_fields\":[],\
+                        \"duplicate_keys\":[],\"unknown_keys\":[]}]}";
+
+        assert_eq!(report.to_json(), expected);
+    }
+}
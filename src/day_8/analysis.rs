@@ -0,0 +1,313 @@
+use std::collections::VecDeque;
+
+use super::instruction::Instruction;
+
+/// Static control flow between instructions.
+///
+/// Node `n` (one past the last instruction) is the exit; reaching it means the program
+/// terminated. Jumps to any other index outside the program have no successor.
+#[derive(Debug, PartialEq)]
+pub struct ControlFlowGraph {
+    successors: Vec<Vec<usize>>,
+}
+
+impl ControlFlowGraph {
+    pub fn new(instructions: &[Instruction]) -> ControlFlowGraph {
+        ControlFlowGraph {
+            successors: instructions
+                .iter()
+                .enumerate()
+                .map(|(index, instruction)| {
+                    Self::successors_of(index, instruction, instructions.len())
+                })
+                .collect(),
+        }
+    }
+
+    pub fn exit(&self) -> usize {
+        self.successors.len()
+    }
+
+    pub fn get_successors(&self, index: usize) -> &[usize] {
+        self.successors
+            .get(index)
+            .map_or(&[], |successors| successors)
+    }
+
+    /// Marks every node, including the exit, that can be reached from `start`.
+    pub fn reachable_from(&self, start: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.exit() + 1];
+        let mut queue = VecDeque::new();
+
+        if start <= self.exit() {
+            reachable[start] = true;
+            queue.push_back(start);
+        }
+
+        while let Some(index) = queue.pop_front() {
+            for &successor in self.get_successors(index) {
+                if !reachable[successor] {
+                    reachable[successor] = true;
+                    queue.push_back(successor);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Marks every node from which some path leads to the exit.
+    pub fn can_reach_exit(&self) -> Vec<bool> {
+        let predecessors = self.predecessors();
+
+        let mut can_reach_exit = vec![false; self.exit() + 1];
+        let mut queue = VecDeque::new();
+
+        can_reach_exit[self.exit()] = true;
+        queue.push_back(self.exit());
+
+        while let Some(index) = queue.pop_front() {
+            for &predecessor in &predecessors[index] {
+                if !can_reach_exit[predecessor] {
+                    can_reach_exit[predecessor] = true;
+                    queue.push_back(predecessor);
+                }
+            }
+        }
+
+        can_reach_exit
+    }
+
+    /// Marks every instruction that lies on some cycle of the graph.
+    pub fn cycle_members(&self) -> Vec<bool> {
+        let mut cycle_members = vec![false; self.exit()];
+
+        for component in self.strongly_connected_components() {
+            let is_cycle = component.len() > 1 || {
+                let index = component[0];
+
+                self.get_successors(index).contains(&index)
+            };
+
+            if is_cycle {
+                for index in component {
+                    cycle_members[index] = true;
+                }
+            }
+        }
+
+        cycle_members
+    }
+
+    /// Groups the nodes into strongly connected components with Kosaraju's algorithm.
+    fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.exit() + 1];
+        let mut finished = Vec::with_capacity(self.exit() + 1);
+
+        for root in 0..=self.exit() {
+            if visited[root] {
+                continue;
+            }
+
+            visited[root] = true;
+
+            let mut stack = vec![(root, 0)];
+
+            while let Some((index, next)) = stack.pop() {
+                match self.get_successors(index).get(next) {
+                    Some(&successor) => {
+                        stack.push((index, next + 1));
+
+                        if !visited[successor] {
+                            visited[successor] = true;
+                            stack.push((successor, 0));
+                        }
+                    }
+                    None => finished.push(index),
+                }
+            }
+        }
+
+        let predecessors = self.predecessors();
+
+        let mut assigned = vec![false; self.exit() + 1];
+        let mut components = Vec::new();
+
+        for &root in finished.iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+
+            assigned[root] = true;
+
+            let mut component = Vec::new();
+            let mut stack = vec![root];
+
+            while let Some(index) = stack.pop() {
+                component.push(index);
+
+                for &predecessor in &predecessors[index] {
+                    if !assigned[predecessor] {
+                        assigned[predecessor] = true;
+                        stack.push(predecessor);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut predecessors = vec![Vec::new(); self.exit() + 1];
+
+        for (index, successors) in self.successors.iter().enumerate() {
+            for &successor in successors {
+                predecessors[successor].push(index);
+            }
+        }
+
+        predecessors
+    }
+
+    /// Returns the indices of the `replacements` that would make a looping program terminate,
+    /// in linear time.
+    ///
+    /// Only an instruction the program actually reaches can matter, and replacing it helps
+    /// exactly when its new successor already leads to the exit. This is exact for programs
+    /// without conditional jumps. A program that already terminates has nothing to fix, so no
+    /// indices are returned for it.
+    pub fn terminating_replacements(&self, replacements: &[(usize, Instruction)]) -> Vec<usize> {
+        let can_reach_exit = self.can_reach_exit();
+
+        if can_reach_exit[0] {
+            return Vec::new();
+        }
+
+        let reachable = self.reachable_from(0);
+
+        replacements
+            .iter()
+            .filter(|(index, _)| reachable.get(*index).copied().unwrap_or(false))
+            .filter(|(index, instruction)| {
+                Self::successors_of(*index, instruction, self.exit())
+                    .iter()
+                    .any(|&successor| can_reach_exit[successor])
+            })
+            .map(|(index, _)| *index)
+            .collect()
+    }
+
+    fn successors_of(index: usize, instruction: &Instruction, length: usize) -> Vec<usize> {
+        let operation = instruction.get_operation();
+
+        let mut offsets = Vec::new();
+
+        match instruction.jump_offset() {
+            Some(offset) => {
+                if operation.is_conditional() {
+                    offsets.push(1);
+                }
+
+                offsets.push(offset);
+            }
+            None => offsets.push(1),
+        }
+
+        let mut successors: Vec<usize> = offsets
+            .into_iter()
+            .map(|offset| (index as i64).saturating_add(offset))
+            .filter(|&target| 0 <= target && target <= length as i64)
+            .map(|target| target as usize)
+            .collect();
+
+        successors.dedup();
+
+        successors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_8::test_utils::{get_instructions, TEST_DATA};
+
+    #[test]
+    fn test_control_flow_graph_new() {
+        let instructions = get_instructions(&["nop +0", "jz a +2", "jmp -5", "jmp +2"]);
+
+        let graph = ControlFlowGraph::new(&instructions);
+
+        assert_eq!(graph.exit(), 4);
+        assert_eq!(graph.get_successors(0), &[1]);
+        assert_eq!(graph.get_successors(1), &[2, 3]);
+        assert!(graph.get_successors(2).is_empty());
+        assert!(graph.get_successors(3).is_empty());
+    }
+
+    #[test]
+    fn test_control_flow_graph_reachability() {
+        let graph = ControlFlowGraph::new(&get_instructions(&TEST_DATA));
+
+        let reachable = graph.reachable_from(0);
+        let can_reach_exit = graph.can_reach_exit();
+
+        let expected_reachable = vec![
+            true, true, true, true, true, false, true, true, false, false,
+        ];
+        let expected_can_reach_exit = vec![
+            false, false, false, false, false, false, false, false, true, true,
+        ];
+
+        assert_eq!(reachable, expected_reachable);
+        assert_eq!(can_reach_exit, expected_can_reach_exit);
+    }
+
+    #[test]
+    fn test_control_flow_graph_cycle_members() {
+        let graph = ControlFlowGraph::new(&get_instructions(&[
+            "jmp +0", "nop +0", "jmp -1", "acc +1", "jz a +2", "jmp -1",
+        ]));
+
+        let result = graph.cycle_members();
+
+        let expected = vec![true, true, true, false, true, true];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_control_flow_graph_terminating_replacements() {
+        let instructions = get_instructions(&TEST_DATA);
+
+        let graph = ControlFlowGraph::new(&instructions);
+
+        let replacements: Vec<(usize, Instruction)> =
+            [(0, "jmp +0"), (2, "nop +4"), (4, "nop -3"), (7, "nop -4")]
+                .iter()
+                .map(|(index, info)| (*index, get_instructions(&[info]).remove(0)))
+                .collect();
+
+        let result = graph.terminating_replacements(&replacements);
+
+        assert_eq!(result, vec![7]);
+    }
+
+    #[test]
+    fn test_control_flow_graph_terminating_replacements_multiple_fixes() {
+        let instructions = get_instructions(&["jmp +2", "jmp +2", "jmp +0", "nop +0"]);
+
+        let graph = ControlFlowGraph::new(&instructions);
+
+        let replacements: Vec<(usize, Instruction)> = [(0, "nop +2"), (2, "nop +0")]
+            .iter()
+            .map(|(index, info)| (*index, get_instructions(&[info]).remove(0)))
+            .collect();
+
+        let result = graph.terminating_replacements(&replacements);
+
+        assert_eq!(result, vec![0, 2]);
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use super::instruction::{Instruction, InstructionSet};
+
+/// Compiles assembly source into instructions for a given `InstructionSet`.
+///
+/// Each line holds at most one instruction. `#` and `;` start comments, and `name:` at the start
+/// of a line defines a label for the instruction that follows it. Labels may be used in place of
+/// a jump offset and are resolved relative to the jumping instruction.
+pub fn assemble(
+    source: &[String],
+    instruction_set: &InstructionSet,
+) -> Result<Vec<Instruction>, String> {
+    let mut labels = HashMap::new();
+    let mut instruction_lines = Vec::new();
+
+    for (line_index, line) in source.iter().enumerate() {
+        let line_number = line_index + 1;
+        let mut code = line.split(['#', ';']).next().unwrap_or("").trim();
+
+        while let Some((label, rest)) = split_label(code) {
+            if labels.insert(label, instruction_lines.len()).is_some() {
+                return Err(format!("Line {}: Duplicate Label {:?}", line_number, label));
+            }
+
+            code = rest;
+        }
+
+        if !code.is_empty() {
+            instruction_lines.push((line_number, code));
+        }
+    }
+
+    instruction_lines
+        .iter()
+        .enumerate()
+        .map(|(instruction_index, (line_number, code))| {
+            assemble_instruction(code, instruction_index, &labels, instruction_set)
+                .map_err(|error| format!("Line {}: {}", line_number, error))
+        })
+        .collect()
+}
+
+fn split_label(code: &str) -> Option<(&str, &str)> {
+    let (label, rest) = code.split_once(':')?;
+    let label = label.trim();
+
+    let is_label = !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+
+    if is_label {
+        Some((label, rest.trim()))
+    } else {
+        None
+    }
+}
+
+fn assemble_instruction(
+    code: &str,
+    instruction_index: usize,
+    labels: &HashMap<&str, usize>,
+    instruction_set: &InstructionSet,
+) -> Result<Instruction, String> {
+    let tokens: Vec<&str> = code
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    let mnemonic = tokens.first().copied().unwrap_or("");
+
+    let jump_operand = instruction_set
+        .get_operation(mnemonic)
+        .and_then(|operation| operation.jump_operand());
+
+    let mut resolved_tokens = vec![String::from(mnemonic)];
+
+    for (operand_index, token) in tokens.iter().skip(1).enumerate() {
+        let resolved_token = match labels.get(token) {
+            Some(&target) if jump_operand == Some(operand_index) => {
+                format!("{:+}", target as i64 - instruction_index as i64)
+            }
+            Some(_) => return Err(format!("Label {:?} Used Outside A Jump Target", token)),
+            None => token.to_string(),
+        };
+
+        resolved_tokens.push(resolved_token);
+    }
+
+    instruction_set.decode(&resolved_tokens.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SOURCE: [&str; 9] = [
+        "# multiply 6 by 7 into acc",
+        "    set a 6",
+        "    set b 7",
+        "loop:",
+        "    acc a        ; add a once per iteration",
+        "    sub b 1",
+        "    jnz b, loop",
+        "end: nop +0",
+        "    jmp end",
+    ];
+
+    #[test]
+    fn test_assemble() {
+        let source: Vec<String> = TEST_SOURCE.iter().map(|s| s.to_string()).collect();
+
+        let result: Vec<String> = assemble(&source, &InstructionSet::extended())
+            .unwrap()
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect();
+
+        let expected = vec![
+            "set a +6", "set b +7", "acc a", "sub b +1", "jnz b -2", "nop +0", "jmp -1",
+        ];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let assemble_lines = |lines: &[&str]| {
+            let source: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+
+            assemble(&source, &InstructionSet::extended())
+        };
+
+        assert_eq!(
+            assemble_lines(&["nop +0", "a: nop +0", "a: nop +0"]),
+            Err(String::from("Line 3: Duplicate Label \"a\""))
+        );
+        assert_eq!(
+            assemble_lines(&["nop +0", "frob a"]),
+            Err(String::from("Line 2: No Operation For: frob"))
+        );
+        assert_eq!(
+            assemble_lines(&["top: set top 1"]),
+            Err(String::from(
+                "Line 1: Label \"top\" Used Outside A Jump Target"
+            ))
+        );
+        assert!(assemble_lines(&["jmp nowhere"]).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use std::fs;
+use std::io::{self, Write};
+
+use super::handheld::{BootCode, Breakpoint};
+use super::instruction::InstructionSet;
+use super::snapshot::Snapshot;
+
+const HELP: &str = "Commands:
+  s [n]   step n instructions (default 1)
+  c       continue to the next breakpoint
+  b <i>   break before instruction i
+  a <v>   break when the accumulator becomes v
+  d       delete all breakpoints
+  t [n]   show the last n trace entries (default 10)
+  p       print the current state
+  r       reset the program
+  m       mark the current state
+  g       go back to the marked state
+  v       view the changes since the mark
+  w <f>   write a snapshot to file f
+  l <f>   load a snapshot from file f
+  q       quit";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Step(usize),
+    Continue,
+    Break(Breakpoint),
+    DeleteBreakpoints,
+    Trace(usize),
+    Print,
+    Reset,
+    Mark,
+    GoBack,
+    ViewChanges,
+    Write(String),
+    Load(String),
+    Help,
+    Quit,
+}
+
+impl Command {
+    fn new(input: &str) -> Result<Command, String> {
+        let mut split_input = input.split_whitespace();
+
+        let name = split_input.next().unwrap_or("h");
+        let argument = split_input.next();
+
+        let command = match (name, argument) {
+            ("s", argument) => Command::Step(Self::parse(argument.unwrap_or("1"))?),
+            ("c", _) => Command::Continue,
+            ("b", Some(argument)) => {
+                Command::Break(Breakpoint::InstructionIndex(Self::parse(argument)?))
+            }
+            ("a", Some(argument)) => {
+                Command::Break(Breakpoint::Accumulator(Self::parse(argument)?))
+            }
+            ("w", Some(argument)) => Command::Write(String::from(argument)),
+            ("l", Some(argument)) => Command::Load(String::from(argument)),
+            ("b", None) | ("a", None) | ("w", None) | ("l", None) => {
+                return Err(format!("Command {:?} Needs An Argument", name))
+            }
+            ("d", _) => Command::DeleteBreakpoints,
+            ("t", argument) => Command::Trace(Self::parse(argument.unwrap_or("10"))?),
+            ("p", _) => Command::Print,
+            ("r", _) => Command::Reset,
+            ("m", _) => Command::Mark,
+            ("g", _) => Command::GoBack,
+            ("v", _) => Command::ViewChanges,
+            ("h", _) => Command::Help,
+            ("q", _) => Command::Quit,
+            _ => return Err(format!("Unknown Command: {:?}", name)),
+        };
+
+        Ok(command)
+    }
+
+    fn parse<T: std::str::FromStr>(argument: &str) -> Result<T, String> {
+        argument
+            .parse()
+            .map_err(|_| format!("Can't Parse Argument: {:?}", argument))
+    }
+}
+
+/// Reads debugger commands from stdin until `q` or the end of input.
+pub fn run_debugger(boot_code: &mut BootCode) {
+    println!("{}", HELP);
+
+    print_state(boot_code);
+
+    let mut mark = None;
+
+    loop {
+        print!("(debug) ");
+
+        let input_buffer = read_line();
+
+        if input_buffer.is_empty() {
+            break;
+        }
+
+        match Command::new(&input_buffer) {
+            Ok(Command::Quit) => break,
+            Ok(command) => apply_command(boot_code, &mut mark, command),
+            Err(error) => println!("{}", error),
+        }
+    }
+}
+
+/// Reads one raw line from stdin; an empty string means the input has ended.
+pub fn read_line() -> String {
+    let mut input_buffer = String::new();
+
+    io::stdout().flush().expect("Could not flush stdout!");
+
+    io::stdin()
+        .read_line(&mut input_buffer)
+        .expect("Failed to read user input!");
+
+    input_buffer
+}
+
+fn apply_command(boot_code: &mut BootCode, mark: &mut Option<Snapshot>, command: Command) {
+    match command {
+        Command::Step(number_of_steps) => {
+            for _ in 0..number_of_steps {
+                if !boot_code.step() {
+                    break;
+                }
+
+                println!("{}", boot_code.get_trace().last().unwrap());
+            }
+
+            if let Some(outcome) = boot_code.get_outcome() {
+                println!("{}", outcome);
+            }
+
+            print_state(boot_code);
+        }
+        Command::Continue => {
+            match boot_code.run_to_breakpoint() {
+                Some(breakpoint) => println!("Hit {:?}", breakpoint),
+                None => match boot_code.get_outcome() {
+                    Some(outcome) => println!("{}", outcome),
+                    None => println!("No breakpoint hit"),
+                },
+            }
+
+            print_state(boot_code);
+        }
+        Command::Break(breakpoint) => boot_code.add_breakpoint(breakpoint),
+        Command::DeleteBreakpoints => boot_code.clear_breakpoints(),
+        Command::Trace(number_of_entries) => {
+            let trace = boot_code.get_trace();
+
+            for entry in &trace[trace.len().saturating_sub(number_of_entries)..] {
+                println!("{}", entry);
+            }
+        }
+        Command::Print => print_state(boot_code),
+        Command::Reset => {
+            boot_code.reset();
+
+            print_state(boot_code);
+        }
+        Command::Mark => *mark = Some(boot_code.snapshot()),
+        Command::GoBack => match mark {
+            Some(snapshot) => {
+                boot_code.restore(snapshot);
+
+                print_state(boot_code);
+            }
+            None => println!("No state marked"),
+        },
+        Command::ViewChanges => match mark {
+            Some(snapshot) => {
+                for difference in snapshot.diff(&boot_code.snapshot()) {
+                    println!("{}", difference);
+                }
+            }
+            None => println!("No state marked"),
+        },
+        Command::Write(path) => {
+            if let Err(error) = fs::write(&path, boot_code.snapshot().to_string()) {
+                println!("Can't Write Snapshot: {}", error);
+            }
+        }
+        Command::Load(path) => {
+            let snapshot = fs::read_to_string(&path)
+                .map_err(|error| error.to_string())
+                .and_then(|text| {
+                    let lines: Vec<String> = text.lines().map(String::from).collect();
+
+                    Snapshot::parse(&lines, &InstructionSet::extended())
+                });
+
+            match snapshot {
+                Ok(snapshot) => {
+                    boot_code.restore(&snapshot);
+
+                    print_state(boot_code);
+                }
+                Err(error) => println!("Can't Load Snapshot: {}", error),
+            }
+        }
+        Command::Help => println!("{}", HELP),
+        Command::Quit => {}
+    }
+}
+
+fn print_state(boot_code: &BootCode) {
+    let next_instruction = boot_code
+        .get_current_instruction()
+        .map_or(String::from("<end of program>"), |instruction| {
+            instruction.to_string()
+        });
+
+    println!(
+        "{:?} | next [{}] {} | acc = {} | breakpoints = {:?}",
+        boot_code.get_status(),
+        boot_code.get_current_instruction_index(),
+        next_instruction,
+        boot_code.get_accumulator(),
+        boot_code.get_breakpoints()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_new() {
+        assert_eq!(Command::new("s\n"), Ok(Command::Step(1)));
+        assert_eq!(Command::new("s 5"), Ok(Command::Step(5)));
+        assert_eq!(Command::new("c"), Ok(Command::Continue));
+        assert_eq!(
+            Command::new("b 42"),
+            Ok(Command::Break(Breakpoint::InstructionIndex(42)))
+        );
+        assert_eq!(
+            Command::new("a -7"),
+            Ok(Command::Break(Breakpoint::Accumulator(-7)))
+        );
+        assert_eq!(Command::new("t"), Ok(Command::Trace(10)));
+        assert_eq!(
+            Command::new("w state.txt"),
+            Ok(Command::Write(String::from("state.txt")))
+        );
+        assert!(Command::new("l").is_err());
+        assert_eq!(Command::new(""), Ok(Command::Help));
+        assert!(Command::new("b").is_err());
+        assert!(Command::new("b x").is_err());
+        assert!(Command::new("x").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use super::analysis::ControlFlowGraph;
+use super::instruction::Instruction;
+
+/// Renders a program as an annotated listing or as a Graphviz graph.
+///
+/// Jumps show where they lead, instructions the program can never reach are marked as
+/// unreachable and instructions that lie on a cycle are marked as part of a loop.
+pub struct Disassembler<'a> {
+    instructions: &'a [Instruction],
+    graph: ControlFlowGraph,
+    reachable: Vec<bool>,
+    cycle_members: Vec<bool>,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(instructions: &'a [Instruction]) -> Disassembler<'a> {
+        let graph = ControlFlowGraph::new(instructions);
+        let reachable = graph.reachable_from(0);
+        let cycle_members = graph.cycle_members();
+
+        Disassembler {
+            instructions,
+            graph,
+            reachable,
+            cycle_members,
+        }
+    }
+
+    pub fn listing(&self) -> String {
+        self.instructions
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| {
+                let target = self
+                    .jump_target(index)
+                    .map_or(String::new(), |target| format!("-> {}", target));
+
+                let line = format!(
+                    "{:>4}  {:<12} {:<8} {}",
+                    index,
+                    instruction.to_string(),
+                    target,
+                    self.get_notes(index).join(", ")
+                );
+
+                format!("{}\n", line.trim_end())
+            })
+            .collect()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph boot_code {\n");
+
+        dot.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let mut attributes = vec![format!("label=\"{}: {}\"", index, instruction)];
+
+            if !self.reachable[index] {
+                attributes.push(String::from("style=dashed"));
+            }
+
+            if self.cycle_members[index] {
+                attributes.push(String::from("color=red"));
+            }
+
+            dot.push_str(&format!("    {} [{}];\n", index, attributes.join(", ")));
+        }
+
+        dot.push_str(&format!(
+            "    {} [label=\"exit\", shape=oval];\n",
+            self.graph.exit()
+        ));
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            for &successor in self.graph.get_successors(index) {
+                let is_taken_branch = instruction.get_operation().is_conditional()
+                    && instruction
+                        .jump_offset()
+                        .map(|offset| (index as i64).saturating_add(offset))
+                        == Some(successor as i64);
+
+                if is_taken_branch {
+                    dot.push_str(&format!(
+                        "    {} -> {} [label=\"taken\"];\n",
+                        index, successor
+                    ));
+                } else {
+                    dot.push_str(&format!("    {} -> {};\n", index, successor));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// Where a jump leads: an instruction index, `exit`, or `out of bounds`.
+    fn jump_target(&self, index: usize) -> Option<String> {
+        let target = (index as i64).saturating_add(self.instructions[index].jump_offset()?);
+
+        let target = if target == self.graph.exit() as i64 {
+            String::from("exit")
+        } else if target < 0 || self.graph.exit() < target as usize {
+            String::from("out of bounds")
+        } else {
+            target.to_string()
+        };
+
+        Some(target)
+    }
+
+    fn get_notes(&self, index: usize) -> Vec<&'static str> {
+        let mut notes = Vec::new();
+
+        if !self.reachable[index] {
+            notes.push("unreachable");
+        }
+
+        if self.cycle_members[index] {
+            notes.push("loop");
+        }
+
+        notes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_8::test_utils::{get_instructions, TEST_DATA};
+
+    #[test]
+    fn test_disassembler_listing() {
+        let instructions = get_instructions(&TEST_DATA);
+
+        let result = Disassembler::new(&instructions).listing();
+
+        let expected = "   0  nop +0
+   1  acc +1                loop
+   2  jmp +4       -> 6     loop
+   3  acc +3                loop
+   4  jmp -3       -> 1     loop
+   5  acc -99               unreachable
+   6  acc +1                loop
+   7  jmp -4       -> 3     loop
+   8  acc +6                unreachable
+";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_disassembler_listing_jump_targets() {
+        let instructions = get_instructions(&["jz a +3", "jmp -2", "jmp +1"]);
+
+        let result = Disassembler::new(&instructions).listing();
+
+        let expected = "   0  jz a +3      -> exit
+   1  jmp -2       -> out of bounds
+   2  jmp +1       -> exit  unreachable
+";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_disassembler_to_dot() {
+        let instructions = get_instructions(&["set a 2", "sub a 1", "jnz a -1", "nop +0"]);
+
+        let result = Disassembler::new(&instructions).to_dot();
+
+        let expected = "digraph boot_code {
+    node [shape=box, fontname=\"monospace\"];
+    0 [label=\"0: set a +2\"];
+    1 [label=\"1: sub a +1\", color=red];
+    2 [label=\"2: jnz a -1\", color=red];
+    3 [label=\"3: nop +0\"];
+    4 [label=\"exit\", shape=oval];
+    0 -> 1;
+    1 -> 2;
+    2 -> 3;
+    2 -> 1 [label=\"taken\"];
+    3 -> 4;
+}
+";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_disassembler_to_dot_jump_to_exit() {
+        let instructions = get_instructions(&["jz a +2", "acc +1"]);
+
+        let result = Disassembler::new(&instructions).to_dot();
+
+        let expected = "digraph boot_code {
+    node [shape=box, fontname=\"monospace\"];
+    0 [label=\"0: jz a +2\"];
+    1 [label=\"1: acc +1\"];
+    2 [label=\"exit\", shape=oval];
+    0 -> 1;
+    0 -> 2 [label=\"taken\"];
+    1 -> 2;
+}
+";
+
+        assert_eq!(result, expected);
+    }
+}
@@ -1,60 +1,123 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
-enum Operation {
-    Accumulate,
-    Jump,
-    Noop,
+use super::analysis::ControlFlowGraph;
+use super::assembler::assemble;
+use super::instruction::{
+    read_register, Instruction, InstructionSet, Operation, Registers, ACCUMULATOR,
+};
+use super::snapshot::Snapshot;
+
+/// One executed instruction and the registers right after it ran.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TraceEntry {
+    pub instruction_index: usize,
+    pub instruction: Instruction,
+    pub registers: Registers,
 }
 
-impl Operation {
-    fn new(operation: &str) -> Operation {
-        match operation {
-            "acc" => Operation::Accumulate,
-            "jmp" => Operation::Jump,
-            "nop" => Operation::Noop,
-            _ => panic!("No Operation For: {}", operation),
-        }
+impl TraceEntry {
+    pub fn accumulator(&self) -> i64 {
+        read_register(&self.registers, ACCUMULATOR)
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct Instruction {
-    operation: Operation,
-    argument: i32,
-}
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{:>4}] {:<12} acc = {}",
+            self.instruction_index,
+            self.instruction.to_string(),
+            self.accumulator()
+        )?;
+
+        for (name, value) in self
+            .registers
+            .iter()
+            .filter(|(name, _)| *name != ACCUMULATOR)
+        {
+            write!(f, " {} = {}", name, value)?;
+        }
 
-impl Instruction {
-    fn new(instruction_info: &str) -> Instruction {
-        let (operation_info, argument_info) = instruction_info.split_at(3);
+        Ok(())
+    }
+}
 
-        let operation = Operation::new(operation_info);
-        let argument = Self::get_argument_value(argument_info.trim_start());
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Breakpoint {
+    InstructionIndex(usize),
+    Accumulator(i64),
+}
 
-        Instruction {
-            operation: operation,
-            argument: argument,
+impl Breakpoint {
+    /// Index breakpoints stop before that instruction runs; accumulator breakpoints stop right
+    /// after the accumulator changes to that value.
+    fn is_hit(&self, boot_code: &BootCode) -> bool {
+        match self {
+            Breakpoint::InstructionIndex(index) => boot_code.current_instruction_index == *index,
+            Breakpoint::Accumulator(value) => {
+                let previous_accumulator = boot_code
+                    .trace
+                    .iter()
+                    .rev()
+                    .nth(1)
+                    .map_or(0, |entry| entry.accumulator());
+
+                boot_code.get_accumulator() == *value && previous_accumulator != *value
+            }
         }
     }
+}
 
-    fn get_argument_value(argument_info: &str) -> i32 {
-        let value_parse_err = format!("Can't Parse Argument Info: {}", argument_info);
-
-        let (sign, value_str) = argument_info.split_at(1);
-
-        let mut value = value_str.parse().expect(&value_parse_err);
+/// Why a program stopped running.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Outcome {
+    /// Execution moved to the instruction right after the last one.
+    Terminated,
+    /// The program was about to repeat a state; `cycle` lists the instructions executed since
+    /// it was first at `entry_index` in that state.
+    LoopDetected {
+        entry_index: usize,
+        cycle: Vec<usize>,
+    },
+    /// The instruction at `instruction_index` tried to move to a target before the first or
+    /// after the end of the program.
+    OutOfBounds {
+        instruction_index: usize,
+        target: i64,
+    },
+    StepLimitExceeded {
+        step_limit: usize,
+    },
+}
 
-        match sign {
-            "+" => value *= 1,
-            "-" => value *= -1,
-            _ => panic!(value_parse_err),
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Terminated => write!(f, "Terminated"),
+            Outcome::LoopDetected { entry_index, cycle } => {
+                let cycle: Vec<String> = cycle.iter().map(|index| index.to_string()).collect();
+
+                write!(
+                    f,
+                    "Loop detected at instruction {}: {}",
+                    entry_index,
+                    cycle.join(" -> ")
+                )
+            }
+            Outcome::OutOfBounds {
+                instruction_index,
+                target,
+            } => write!(
+                f,
+                "Instruction {} jumped out of bounds to {}",
+                instruction_index, target
+            ),
+            Outcome::StepLimitExceeded { step_limit } => {
+                write!(f, "Step limit of {} exceeded", step_limit)
+            }
         }
-
-        value
-    }
-
-    fn change_operation(&mut self, operation: Operation) {
-        self.operation = operation;
     }
 }
 
@@ -62,188 +125,354 @@ impl Instruction {
 pub enum Status {
     Initialized,
     Working,
-    Finished,
+    Stopped(Outcome),
 }
 
 #[derive(Debug, PartialEq)]
 pub struct BootCode {
     instructions: Vec<Instruction>,
     current_instruction_index: usize,
-    accumulator: i32,
+    registers: Registers,
     status: Status,
+    has_conditional_jumps: bool,
+    visited_states: HashMap<(usize, Option<Registers>), usize>,
+    trace: Vec<TraceEntry>,
+    breakpoints: Vec<Breakpoint>,
+    step_limit: Option<usize>,
 }
 
 impl BootCode {
+    /// Builds a day 8 program, which only uses the `handheld` instruction set.
     pub fn new(instructions_info: &[String]) -> BootCode {
-        let instructions = instructions_info
-            .iter()
-            .map(|s| Instruction::new(s))
-            .collect();
+        let instructions = assemble(instructions_info, &InstructionSet::handheld())
+            .unwrap_or_else(|error| panic!("Can't Assemble Boot Code: {}", error));
+
+        BootCode::from_instructions(instructions)
+    }
+
+    pub fn assemble(
+        source: &[String],
+        instruction_set: &InstructionSet,
+    ) -> Result<BootCode, String> {
+        assemble(source, instruction_set).map(BootCode::from_instructions)
+    }
+
+    pub fn from_instructions(instructions: Vec<Instruction>) -> BootCode {
+        let has_conditional_jumps = Self::has_conditional_jumps(&instructions);
 
         BootCode {
-            instructions: instructions,
+            instructions,
             current_instruction_index: 0,
-            accumulator: 0,
+            registers: Registers::new(),
             status: Status::Initialized,
+            has_conditional_jumps,
+            visited_states: HashMap::new(),
+            trace: Vec::new(),
+            breakpoints: Vec::new(),
+            step_limit: None,
         }
     }
 
-    pub fn execute(&mut self) {
-        let mut executed_instruction_indices = HashSet::new();
+    /// Stops `execute` and `run_until` once this many instructions have run since the last reset.
+    pub fn with_step_limit(mut self, step_limit: usize) -> BootCode {
+        self.step_limit = Some(step_limit);
+
+        self
+    }
+
+    /// Runs until the program stops and reports why it did.
+    pub fn execute(&mut self) -> Outcome {
+        self.run_until(|_| false);
+
+        self.get_outcome()
+            .cloned()
+            .expect("Program Stopped Without An Outcome!")
+    }
+
+    /// Executes the instruction at the current index, returning `false` if the program stopped
+    /// instead. Like `run_until`, it stops before a repeated state or past the step limit.
+    pub fn step(&mut self) -> bool {
+        self.run_until(|_| true)
+    }
+
+    fn execute_instruction(&mut self) -> bool {
+        if let Status::Stopped(_) = self.status {
+            return false;
+        }
+
+        let current_instruction = match self.instructions.get(self.current_instruction_index) {
+            Some(instruction) => instruction.clone(),
+            None => {
+                self.status = Status::Stopped(Outcome::Terminated);
+
+                return false;
+            }
+        };
+
+        let instruction_index = self.current_instruction_index;
 
+        self.visited_states
+            .insert(self.get_state_key(), self.trace.len());
         self.status = Status::Working;
 
+        let offset = current_instruction.execute(&mut self.registers);
+
+        self.trace.push(TraceEntry {
+            instruction_index,
+            instruction: current_instruction,
+            registers: self.registers.clone(),
+        });
+
+        self.jump(offset);
+
+        true
+    }
+
+    /// Steps until `predicate` holds after a step, returning `false` if the program stopped
+    /// first. A program about to repeat a state or exceeding the step limit is stopped here.
+    pub fn run_until<P>(&mut self, mut predicate: P) -> bool
+    where
+        P: FnMut(&BootCode) -> bool,
+    {
         loop {
-            if executed_instruction_indices.contains(&self.current_instruction_index) {
-                break;
+            if let Status::Stopped(_) = self.status {
+                return false;
             }
 
-            executed_instruction_indices.insert(self.current_instruction_index);
+            if let Some(&first_visit) = self.visited_states.get(&self.get_state_key()) {
+                self.status = Status::Stopped(Outcome::LoopDetected {
+                    entry_index: self.current_instruction_index,
+                    cycle: self.trace[first_visit..]
+                        .iter()
+                        .map(|entry| entry.instruction_index)
+                        .collect(),
+                });
 
-            let current_instruction;
+                return false;
+            }
+
+            if let Some(step_limit) = self.step_limit {
+                if step_limit <= self.trace.len() {
+                    self.status = Status::Stopped(Outcome::StepLimitExceeded { step_limit });
 
-            if let Some(instruction) = self.instructions.get(self.current_instruction_index) {
-                current_instruction = instruction.clone();
-            } else {
-                self.status = Status::Finished;
+                    return false;
+                }
+            }
 
-                break;
+            if !self.execute_instruction() {
+                return false;
             }
 
-            match current_instruction.operation {
-                Operation::Accumulate => self.accumulate(current_instruction.argument),
-                Operation::Jump => self.jump(current_instruction.argument),
-                Operation::Noop => self.no_op(),
+            if predicate(self) {
+                return true;
             }
         }
     }
 
-    pub fn execute_self_correcting(&mut self) {
-        let operations_to_swap = vec![Operation::Jump, Operation::Noop].into_iter().collect();
-        let indices_to_swap = self.get_jndices_of_operation_types(&operations_to_swap);
+    /// Runs until any breakpoint is hit, returning the breakpoint that stopped execution.
+    pub fn run_to_breakpoint(&mut self) -> Option<Breakpoint> {
+        let breakpoints = self.breakpoints.clone();
 
-        for index_to_swap in indices_to_swap.into_iter() {
-            let old_operation = self.instructions[index_to_swap].operation;
+        let mut hit_breakpoint = None;
 
-            let new_operation = match old_operation {
-                Operation::Accumulate => Operation::Accumulate,
-                Operation::Jump => Operation::Noop,
-                Operation::Noop => Operation::Jump,
-            };
+        self.run_until(|boot_code| {
+            hit_breakpoint = breakpoints
+                .iter()
+                .find(|breakpoint| breakpoint.is_hit(boot_code))
+                .copied();
 
-            self.instructions
-                .get_mut(index_to_swap)
-                .unwrap()
-                .change_operation(new_operation);
+            hit_breakpoint.is_some()
+        });
 
-            self.execute();
+        hit_breakpoint
+    }
 
-            if self.status == Status::Finished {
-                break;
-            }
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
 
-            self.instructions
-                .get_mut(index_to_swap)
-                .unwrap()
-                .change_operation(old_operation);
+    pub fn get_breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn get_instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
 
-            self.reset();
+    pub fn get_trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    pub fn get_current_instruction_index(&self) -> usize {
+        self.current_instruction_index
+    }
+
+    pub fn get_current_instruction(&self) -> Option<&Instruction> {
+        self.instructions.get(self.current_instruction_index)
+    }
+
+    pub fn get_status(&self) -> &Status {
+        &self.status
+    }
+
+    pub fn get_outcome(&self) -> Option<&Outcome> {
+        match &self.status {
+            Status::Stopped(outcome) => Some(outcome),
+            _ => None,
         }
     }
 
-    pub fn get_accumulator(&self) -> i32 {
-        self.accumulator
+    /// Repairs the first corrupted instruction, if any, and runs the program.
+    pub fn execute_self_correcting(&mut self) {
+        if let Some(&index_to_swap) = self.find_corrupted_instructions().first() {
+            self.instructions[index_to_swap] = self.get_swapped_instruction(index_to_swap);
+        }
+
+        self.execute();
     }
 
+    /// Indices of every `jmp`/`nop` whose swap alone would make the program terminate.
+    pub fn find_corrupted_instructions(&self) -> Vec<usize> {
+        let instruction_set = InstructionSet::handheld();
+
+        let operations_to_swap = vec![
+            instruction_set.get_operation("jmp").unwrap(),
+            instruction_set.get_operation("nop").unwrap(),
+        ]
+        .into_iter()
+        .collect();
+
+        let replacements: Vec<(usize, Instruction)> = self
+            .get_jndices_of_operation_types(&operations_to_swap)
+            .into_iter()
+            .map(|index| (index, self.get_swapped_instruction(index)))
+            .collect();
+
+        ControlFlowGraph::new(&self.instructions).terminating_replacements(&replacements)
+    }
+
+    pub fn get_accumulator(&self) -> i64 {
+        self.get_register(ACCUMULATOR)
+    }
+
+    pub fn get_register(&self, name: &str) -> i64 {
+        read_register(&self.registers, name)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            instructions: self.instructions.clone(),
+            current_instruction_index: self.current_instruction_index,
+            registers: self.registers.clone(),
+            status: self.status.clone(),
+        }
+    }
+
+    /// Continues from `snapshot` with an empty trace, so loops are only detected from there on;
+    /// breakpoints and the step limit are kept.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.instructions = snapshot.instructions.clone();
+        self.current_instruction_index = snapshot.current_instruction_index;
+        self.registers = snapshot.registers.clone();
+        self.status = snapshot.status.clone();
+        self.has_conditional_jumps = Self::has_conditional_jumps(&self.instructions);
+        self.visited_states.clear();
+        self.trace.clear();
+    }
+
+    /// Rewinds to the first instruction and clears the trace; breakpoints are kept.
     pub fn reset(&mut self) {
         self.current_instruction_index = 0;
-        self.accumulator = 0;
+        self.registers.clear();
         self.status = Status::Initialized;
+        self.visited_states.clear();
+        self.trace.clear();
     }
 
     fn get_jndices_of_operation_types(&self, operation_types: &HashSet<Operation>) -> Vec<usize> {
         self.instructions
             .iter()
             .enumerate()
-            .filter(|(_, instruction)| operation_types.contains(&instruction.operation))
+            .filter(|(_, instruction)| operation_types.contains(&instruction.get_operation()))
             .map(|(index, _)| index)
             .collect()
     }
-    fn accumulate(&mut self, value: i32) {
-        self.accumulator += value;
-        self.current_instruction_index += 1;
-    }
 
-    fn jump(&mut self, value: i32) {
-        self.current_instruction_index = ((self.current_instruction_index as i32) + value) as usize;
-    }
+    fn get_swapped_instruction(&self, index: usize) -> Instruction {
+        let instruction_set = InstructionSet::handheld();
+        let jump = instruction_set.get_operation("jmp").unwrap();
+        let noop = instruction_set.get_operation("nop").unwrap();
 
-    fn no_op(&mut self) {
-        self.current_instruction_index += 1;
-    }
-}
+        let mut instruction = self.instructions[index].clone();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let new_operation = if instruction.get_operation() == jump {
+            noop
+        } else {
+            jump
+        };
 
-    const TEST_DATA: [&str; 9] = [
-        "nop +0", "acc +1", "jmp +4", "acc +3", "jmp -3", "acc -99", "acc +1", "jmp -4", "acc +6",
-    ];
+        instruction
+            .change_operation(new_operation)
+            .expect("jmp And nop Take The Same Operands");
 
-    #[test]
-    fn test_operation_new() {
-        let result_1 = Operation::new("acc");
-        let result_2 = Operation::new("jmp");
-        let result_3 = Operation::new("nop");
+        instruction
+    }
 
-        let expected_1 = Operation::Accumulate;
-        let expected_2 = Operation::Jump;
-        let expected_3 = Operation::Noop;
+    fn has_conditional_jumps(instructions: &[Instruction]) -> bool {
+        instructions
+            .iter()
+            .any(|instruction| instruction.get_operation().is_conditional())
+    }
 
-        assert_eq!(result_1, expected_1);
-        assert_eq!(result_2, expected_2);
-        assert_eq!(result_3, expected_3);
+    /// Without conditional jumps the registers can't change the control flow, so revisiting an
+    /// instruction already means the program loops forever. Otherwise the registers have to
+    /// repeat as well.
+    fn get_state_key(&self) -> (usize, Option<Registers>) {
+        if self.has_conditional_jumps {
+            (self.current_instruction_index, Some(self.registers.clone()))
+        } else {
+            (self.current_instruction_index, None)
+        }
     }
 
-    #[test]
-    fn test_instruction_get_argument_value() {
-        let result_1 = Instruction::get_argument_value("-99");
-        let result_2 = Instruction::get_argument_value("+4");
-        let result_3 = Instruction::get_argument_value("+0");
+    fn jump(&mut self, offset: i64) {
+        let target = (self.current_instruction_index as i64).saturating_add(offset);
 
-        let expected_1 = -99;
-        let expected_2 = 4;
-        let expected_3 = 0;
+        if target < 0 || (self.instructions.len() as i64) < target {
+            self.status = Status::Stopped(Outcome::OutOfBounds {
+                instruction_index: self.current_instruction_index,
+                target,
+            });
+        } else {
+            self.current_instruction_index = target as usize;
 
-        assert_eq!(result_1, expected_1);
-        assert_eq!(result_2, expected_2);
-        assert_eq!(result_3, expected_3);
+            if self.current_instruction_index == self.instructions.len() {
+                self.status = Status::Stopped(Outcome::Terminated);
+            }
+        }
     }
+}
 
-    #[test]
-    fn test_instruction_new() {
-        let result_1 = Instruction::new("acc -99");
-        let result_2 = Instruction::new("jmp +4");
-        let result_3 = Instruction::new("nop +0");
-
-        let expected_1 = Instruction {
-            operation: Operation::Accumulate,
-            argument: -99,
-        };
-        let expected_2 = Instruction {
-            operation: Operation::Jump,
-            argument: 4,
-        };
-        let expected_3 = Instruction {
-            operation: Operation::Noop,
-            argument: 0,
-        };
-
-        assert_eq!(result_1, expected_1);
-        assert_eq!(result_2, expected_2);
-        assert_eq!(result_3, expected_3);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_8::test_utils::TEST_DATA;
+
+    const TEST_EXTENDED_SOURCE: [&str; 7] = [
+        "    set a 6",
+        "    set b 7",
+        "loop:",
+        "    acc a",
+        "    sub b 1",
+        "    jnz b loop",
+        "    nop +0",
+    ];
 
     #[test]
     fn test_boot_code_new() {
@@ -251,13 +480,18 @@ mod tests {
 
         let result = BootCode::new(&input);
 
-        let expected_instructions = input.iter().map(|s| Instruction::new(s)).collect();
+        let expected_instructions = assemble(&input, &InstructionSet::handheld()).unwrap();
 
         let expected = BootCode {
             instructions: expected_instructions,
             current_instruction_index: 0,
-            accumulator: 0,
+            registers: Registers::new(),
             status: Status::Initialized,
+            has_conditional_jumps: false,
+            visited_states: HashMap::new(),
+            trace: Vec::new(),
+            breakpoints: Vec::new(),
+            step_limit: None,
         };
 
         assert_eq!(result, expected);
@@ -269,18 +503,78 @@ mod tests {
 
         let mut boot_code = BootCode::new(&input);
 
-        boot_code.execute();
-
+        let result_outcome = boot_code.execute();
         let result_status = boot_code.status.clone();
         let result_accumulator = boot_code.get_accumulator();
 
-        let expected_status = Status::Working;
+        let expected_outcome = Outcome::LoopDetected {
+            entry_index: 1,
+            cycle: vec![1, 2, 6, 7, 3, 4],
+        };
+        let expected_status = Status::Stopped(expected_outcome.clone());
         let expected_accumulator = 5;
 
+        assert_eq!(result_outcome, expected_outcome);
         assert_eq!(result_status, expected_status);
         assert_eq!(result_accumulator, expected_accumulator);
     }
 
+    #[test]
+    fn test_boot_code_execute_out_of_bounds() {
+        let execute_lines = |lines: &[&str]| {
+            let input: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+
+            let mut boot_code = BootCode::new(&input);
+
+            (
+                boot_code.execute(),
+                boot_code.get_current_instruction_index(),
+            )
+        };
+
+        assert_eq!(
+            execute_lines(&["acc +1", "jmp -2"]),
+            (
+                Outcome::OutOfBounds {
+                    instruction_index: 1,
+                    target: -1
+                },
+                1
+            )
+        );
+        assert_eq!(
+            execute_lines(&["jmp +3", "acc +1"]),
+            (
+                Outcome::OutOfBounds {
+                    instruction_index: 0,
+                    target: 3
+                },
+                0
+            )
+        );
+        assert_eq!(
+            execute_lines(&["jmp +2", "acc +1"]),
+            (Outcome::Terminated, 2)
+        );
+    }
+
+    #[test]
+    fn test_boot_code_execute_step_limit() {
+        let source: Vec<String> = vec![String::from("loop: add a 1"), String::from("jgz a loop")];
+
+        let mut boot_code = BootCode::assemble(&source, &InstructionSet::extended())
+            .unwrap()
+            .with_step_limit(10);
+
+        let result = boot_code.execute();
+
+        let expected = Outcome::StepLimitExceeded { step_limit: 10 };
+
+        assert_eq!(result, expected);
+        assert_eq!(boot_code.get_register("a"), 5);
+        assert!(!boot_code.step());
+    }
+
     #[test]
     fn test_boot_code_execute_self_correcting() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
@@ -292,20 +586,40 @@ mod tests {
         let result_status = boot_code.status.clone();
         let result_accumulator = boot_code.get_accumulator();
 
-        let expected_status = Status::Finished;
+        let expected_status = Status::Stopped(Outcome::Terminated);
         let expected_accumulator = 8;
 
         assert_eq!(result_status, expected_status);
         assert_eq!(result_accumulator, expected_accumulator);
     }
 
+    #[test]
+    fn test_boot_code_find_corrupted_instructions() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let boot_code = BootCode::new(&input);
+
+        let result = boot_code.find_corrupted_instructions();
+
+        let expected = vec![7];
+
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn test_boot_code_get_jndices_of_operation_types() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
 
         let boot_code = BootCode::new(&input);
 
-        let operation_types = vec![Operation::Jump, Operation::Noop].into_iter().collect();
+        let instruction_set = InstructionSet::handheld();
+
+        let operation_types = vec![
+            instruction_set.get_operation("jmp").unwrap(),
+            instruction_set.get_operation("nop").unwrap(),
+        ]
+        .into_iter()
+        .collect();
 
         let result = boot_code.get_jndices_of_operation_types(&operation_types);
 
@@ -313,4 +627,127 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_boot_code_step() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut boot_code = BootCode::new(&input);
+
+        assert!(boot_code.step());
+        assert!(boot_code.step());
+        assert!(boot_code.step());
+
+        let result_trace: Vec<(usize, String, i64)> = boot_code
+            .get_trace()
+            .iter()
+            .map(|entry| {
+                (
+                    entry.instruction_index,
+                    entry.instruction.to_string(),
+                    entry.accumulator(),
+                )
+            })
+            .collect();
+
+        let expected_trace = vec![
+            (0, String::from("nop +0"), 0),
+            (1, String::from("acc +1"), 1),
+            (2, String::from("jmp +4"), 1),
+        ];
+
+        assert_eq!(result_trace, expected_trace);
+        assert_eq!(boot_code.get_current_instruction_index(), 6);
+        assert_eq!(boot_code.get_status(), &Status::Working);
+    }
+
+    #[test]
+    fn test_boot_code_step_past_end() {
+        let input = vec![String::from("acc +3")];
+
+        let mut boot_code = BootCode::new(&input);
+
+        assert!(boot_code.step());
+        assert_eq!(
+            boot_code.get_status(),
+            &Status::Stopped(Outcome::Terminated)
+        );
+        assert!(!boot_code.step());
+        assert_eq!(boot_code.get_accumulator(), 3);
+    }
+
+    #[test]
+    fn test_boot_code_step_into_loop() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut boot_code = BootCode::new(&input);
+
+        let result = (0..20).filter(|_| boot_code.step()).count();
+
+        assert_eq!(result, 7);
+        assert_eq!(boot_code.get_accumulator(), 5);
+        assert!(matches!(
+            boot_code.get_outcome(),
+            Some(Outcome::LoopDetected { entry_index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_boot_code_run_until() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut boot_code = BootCode::new(&input);
+
+        let result_1 = boot_code.run_until(|boot_code| boot_code.get_accumulator() == 2);
+        let result_2 = boot_code.run_until(|boot_code| boot_code.get_accumulator() == 100);
+
+        assert!(result_1);
+        assert!(!result_2);
+        assert_eq!(boot_code.get_accumulator(), 5);
+        assert_eq!(boot_code.get_trace().len(), 7);
+    }
+
+    #[test]
+    fn test_boot_code_run_to_breakpoint() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut boot_code = BootCode::new(&input);
+
+        boot_code.add_breakpoint(Breakpoint::InstructionIndex(3));
+        boot_code.add_breakpoint(Breakpoint::Accumulator(1));
+
+        let result_1 = boot_code.run_to_breakpoint();
+        let result_2 = boot_code.run_to_breakpoint();
+        let result_3 = boot_code.run_to_breakpoint();
+
+        assert_eq!(result_1, Some(Breakpoint::Accumulator(1)));
+        assert_eq!(result_2, Some(Breakpoint::InstructionIndex(3)));
+        assert_eq!(result_3, None);
+        assert_eq!(boot_code.get_accumulator(), 5);
+
+        boot_code.reset();
+
+        assert!(boot_code.get_trace().is_empty());
+        assert_eq!(boot_code.get_breakpoints().len(), 2);
+    }
+
+    #[test]
+    fn test_boot_code_assemble_and_execute() {
+        let source: Vec<String> = TEST_EXTENDED_SOURCE.iter().map(|s| s.to_string()).collect();
+
+        let mut boot_code = BootCode::assemble(&source, &InstructionSet::extended()).unwrap();
+
+        boot_code.execute();
+
+        assert_eq!(
+            boot_code.get_status(),
+            &Status::Stopped(Outcome::Terminated)
+        );
+        assert_eq!(boot_code.get_accumulator(), 42);
+        assert_eq!(boot_code.get_register("b"), 0);
+        assert_eq!(
+            boot_code.get_trace().last().unwrap().to_string(),
+            "[   5] nop +0       acc = 42 a = 6 b = 0"
+        );
+    }
 }
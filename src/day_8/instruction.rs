@@ -0,0 +1,521 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+pub const ACCUMULATOR: &str = "acc";
+
+pub type Registers = BTreeMap<String, i64>;
+
+pub fn read_register(registers: &Registers, name: &str) -> i64 {
+    registers.get(name).copied().unwrap_or(0)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Operand {
+    Immediate(i64),
+    Register(String),
+}
+
+impl Operand {
+    /// Numbers (optionally signed) are immediates; identifiers are register names.
+    pub fn new(operand_info: &str) -> Result<Operand, String> {
+        if let Ok(value) = operand_info.parse() {
+            return Ok(Operand::Immediate(value));
+        }
+
+        let mut characters = operand_info.chars();
+
+        let is_identifier = characters
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && characters.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        if is_identifier {
+            Ok(Operand::Register(String::from(operand_info)))
+        } else {
+            Err(format!("Can't Parse Operand: {:?}", operand_info))
+        }
+    }
+
+    pub fn value(&self, registers: &Registers) -> i64 {
+        match self {
+            Operand::Immediate(value) => *value,
+            Operand::Register(name) => read_register(registers, name),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Immediate(value) => write!(f, "{:+}", value),
+            Operand::Register(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Runs an instruction against the registers and returns the offset to the next instruction.
+pub type Executor = fn(&[Operand], &mut Registers) -> i64;
+
+/// One mnemonic of an `InstructionSet`.
+///
+/// Operations that move the instruction index declare which operand holds the relative jump
+/// target, so the assembler can resolve labels there and analyses can follow the jump.
+/// Operations that write a register declare which operand names it.
+#[derive(Clone, Copy)]
+pub struct Operation {
+    mnemonic: &'static str,
+    arity: usize,
+    executor: Executor,
+    jump_operand: Option<usize>,
+    conditional: bool,
+    register_operand: Option<usize>,
+}
+
+impl Operation {
+    pub fn new(mnemonic: &'static str, arity: usize, executor: Executor) -> Operation {
+        Operation {
+            mnemonic,
+            arity,
+            executor,
+            jump_operand: None,
+            conditional: false,
+            register_operand: None,
+        }
+    }
+
+    pub fn with_jump_operand(mut self, jump_operand: usize, conditional: bool) -> Operation {
+        self.jump_operand = Some(jump_operand);
+        self.conditional = conditional;
+
+        self
+    }
+
+    pub fn with_register_operand(mut self, register_operand: usize) -> Operation {
+        self.register_operand = Some(register_operand);
+
+        self
+    }
+
+    pub fn jump_operand(&self) -> Option<usize> {
+        self.jump_operand
+    }
+
+    pub fn is_conditional(&self) -> bool {
+        self.conditional
+    }
+}
+
+impl fmt::Debug for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Operation({})", self.mnemonic)
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.mnemonic)
+    }
+}
+
+impl PartialEq for Operation {
+    fn eq(&self, other: &Operation) -> bool {
+        self.mnemonic == other.mnemonic && self.arity == other.arity
+    }
+}
+
+impl Eq for Operation {}
+
+impl Hash for Operation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.mnemonic.hash(state);
+        self.arity.hash(state);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Instruction {
+    operation: Operation,
+    arguments: Vec<Operand>,
+}
+
+impl Instruction {
+    pub fn new(operation: Operation, arguments: Vec<Operand>) -> Result<Instruction, String> {
+        if arguments.len() != operation.arity {
+            return Err(format!(
+                "{} Takes {} Operand(s), Got {}",
+                operation.mnemonic,
+                operation.arity,
+                arguments.len()
+            ));
+        }
+
+        if let Some(Operand::Register(name)) = operation
+            .jump_operand
+            .and_then(|jump_operand| arguments.get(jump_operand))
+        {
+            return Err(format!(
+                "{} Needs A Constant Jump Offset, Got Register {:?}",
+                operation.mnemonic, name
+            ));
+        }
+
+        if let Some(Operand::Immediate(value)) = operation
+            .register_operand
+            .and_then(|register_operand| arguments.get(register_operand))
+        {
+            return Err(format!(
+                "{} Needs A Register To Write, Got {}",
+                operation.mnemonic, value
+            ));
+        }
+
+        Ok(Instruction {
+            operation,
+            arguments,
+        })
+    }
+
+    pub fn get_operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// The constant offset this instruction may jump by, if its operation jumps at all.
+    pub fn jump_offset(&self) -> Option<i64> {
+        match self
+            .operation
+            .jump_operand
+            .and_then(|jump_operand| self.arguments.get(jump_operand))
+        {
+            Some(Operand::Immediate(offset)) => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// Operations without a jump operand always continue with the next instruction, whatever
+    /// offset their executor returns.
+    pub fn execute(&self, registers: &mut Registers) -> i64 {
+        let offset = (self.operation.executor)(&self.arguments, registers);
+
+        match self.operation.jump_operand {
+            Some(_) => offset,
+            None => 1,
+        }
+    }
+
+    /// Swaps the operation while keeping the operands, which have to suit the new operation.
+    pub fn change_operation(&mut self, operation: Operation) -> Result<(), String> {
+        *self = Instruction::new(operation, self.arguments.clone())?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.operation)?;
+
+        for argument in &self.arguments {
+            write!(f, " {}", argument)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The mnemonics a program may use.
+///
+/// `handheld` is the day 8 set (`acc`, `jmp`, `nop`); `extended` adds general registers,
+/// arithmetic, which wraps around on overflow, and conditional jumps. Further operations can be
+/// plugged in with `with_operation`. An operation only moves anywhere but the next instruction
+/// if it is registered `with_jump_operand`, so the control flow analyses can follow it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InstructionSet {
+    operations: HashMap<&'static str, Operation>,
+}
+
+impl InstructionSet {
+    pub fn new() -> InstructionSet {
+        InstructionSet {
+            operations: HashMap::new(),
+        }
+    }
+
+    pub fn handheld() -> InstructionSet {
+        InstructionSet::new()
+            .with_operation(Operation::new("acc", 1, |arguments, registers| {
+                let value = arguments[0].value(registers);
+
+                let accumulator = registers.entry(String::from(ACCUMULATOR)).or_insert(0);
+
+                *accumulator = accumulator.wrapping_add(value);
+
+                1
+            }))
+            .with_operation(
+                Operation::new("jmp", 1, |arguments, registers| {
+                    arguments[0].value(registers)
+                })
+                .with_jump_operand(0, false),
+            )
+            .with_operation(Operation::new("nop", 1, |_, _| 1))
+    }
+
+    pub fn extended() -> InstructionSet {
+        InstructionSet::handheld()
+            .with_operation(
+                Operation::new("set", 2, |arguments, registers| {
+                    Self::update_register(arguments, registers, |_, value| value)
+                })
+                .with_register_operand(0),
+            )
+            .with_operation(
+                Operation::new("add", 2, |arguments, registers| {
+                    Self::update_register(arguments, registers, |current, value| {
+                        current.wrapping_add(value)
+                    })
+                })
+                .with_register_operand(0),
+            )
+            .with_operation(
+                Operation::new("sub", 2, |arguments, registers| {
+                    Self::update_register(arguments, registers, |current, value| {
+                        current.wrapping_sub(value)
+                    })
+                })
+                .with_register_operand(0),
+            )
+            .with_operation(
+                Operation::new("mul", 2, |arguments, registers| {
+                    Self::update_register(arguments, registers, |current, value| {
+                        current.wrapping_mul(value)
+                    })
+                })
+                .with_register_operand(0),
+            )
+            .with_operation(
+                Operation::new("jz", 2, |arguments, registers| {
+                    Self::jump_if(arguments, registers, |value| value == 0)
+                })
+                .with_jump_operand(1, true),
+            )
+            .with_operation(
+                Operation::new("jnz", 2, |arguments, registers| {
+                    Self::jump_if(arguments, registers, |value| value != 0)
+                })
+                .with_jump_operand(1, true),
+            )
+            .with_operation(
+                Operation::new("jgz", 2, |arguments, registers| {
+                    Self::jump_if(arguments, registers, |value| 0 < value)
+                })
+                .with_jump_operand(1, true),
+            )
+    }
+
+    pub fn with_operation(mut self, operation: Operation) -> InstructionSet {
+        self.operations.insert(operation.mnemonic, operation);
+
+        self
+    }
+
+    pub fn get_operation(&self, mnemonic: &str) -> Option<Operation> {
+        self.operations.get(mnemonic).copied()
+    }
+
+    /// Parses a single `mnemonic operand operand ...` line; operands may be separated by spaces
+    /// or commas.
+    pub fn decode(&self, instruction_info: &str) -> Result<Instruction, String> {
+        let mut tokens = instruction_info
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty());
+
+        let mnemonic = tokens
+            .next()
+            .ok_or_else(|| format!("Empty Instruction: {:?}", instruction_info))?;
+
+        let operation = self
+            .get_operation(mnemonic)
+            .ok_or_else(|| format!("No Operation For: {}", mnemonic))?;
+
+        let arguments = tokens
+            .map(Operand::new)
+            .collect::<Result<Vec<Operand>, String>>()?;
+
+        Instruction::new(operation, arguments)
+    }
+
+    fn update_register<F>(arguments: &[Operand], registers: &mut Registers, update: F) -> i64
+    where
+        F: Fn(i64, i64) -> i64,
+    {
+        if let Operand::Register(name) = &arguments[0] {
+            let value = arguments[1].value(registers);
+            let register = registers.entry(name.clone()).or_insert(0);
+
+            *register = update(*register, value);
+        }
+
+        1
+    }
+
+    fn jump_if<F>(arguments: &[Operand], registers: &Registers, condition: F) -> i64
+    where
+        F: Fn(i64) -> bool,
+    {
+        if condition(arguments[0].value(registers)) {
+            arguments[1].value(registers)
+        } else {
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operand_new() {
+        assert_eq!(Operand::new("-99"), Ok(Operand::Immediate(-99)));
+        assert_eq!(Operand::new("+4"), Ok(Operand::Immediate(4)));
+        assert_eq!(Operand::new("0"), Ok(Operand::Immediate(0)));
+        assert_eq!(
+            Operand::new("r_1"),
+            Ok(Operand::Register(String::from("r_1")))
+        );
+        assert!(Operand::new("1a").is_err());
+        assert!(Operand::new("+").is_err());
+    }
+
+    #[test]
+    fn test_instruction_set_decode() {
+        let instruction_set = InstructionSet::handheld();
+
+        let result_1 = instruction_set.decode("acc -99").unwrap();
+        let result_2 = instruction_set.decode("jmp +4").unwrap();
+        let result_3 = instruction_set.decode("nop +0").unwrap();
+
+        let expected_1 = Instruction {
+            operation: instruction_set.get_operation("acc").unwrap(),
+            arguments: vec![Operand::Immediate(-99)],
+        };
+        let expected_2 = Instruction {
+            operation: instruction_set.get_operation("jmp").unwrap(),
+            arguments: vec![Operand::Immediate(4)],
+        };
+        let expected_3 = Instruction {
+            operation: instruction_set.get_operation("nop").unwrap(),
+            arguments: vec![Operand::Immediate(0)],
+        };
+
+        assert_eq!(result_1, expected_1);
+        assert_eq!(result_2, expected_2);
+        assert_eq!(result_3, expected_3);
+        assert!(instruction_set.decode("mul a 2").is_err());
+        assert!(instruction_set.decode("acc +1 +2").is_err());
+        assert!(instruction_set.decode("jmp a").is_err());
+        assert!(InstructionSet::extended().decode("set 1 2").is_err());
+    }
+
+    #[test]
+    fn test_instruction_execute() {
+        let instruction_set = InstructionSet::extended();
+
+        let mut registers = Registers::new();
+
+        let offsets: Vec<i64> = [
+            "set a 6", "mul a, 7", "sub a 2", "acc a", "jz a +5", "jnz a -3",
+        ]
+        .iter()
+        .map(|info| instruction_set.decode(info).unwrap())
+        .map(|instruction| instruction.execute(&mut registers))
+        .collect();
+
+        assert_eq!(offsets, vec![1, 1, 1, 1, 1, -3]);
+        assert_eq!(read_register(&registers, "a"), 40);
+        assert_eq!(read_register(&registers, ACCUMULATOR), 40);
+        assert_eq!(read_register(&registers, "b"), 0);
+    }
+
+    #[test]
+    fn test_instruction_change_operation() {
+        let instruction_set = InstructionSet::extended();
+
+        let mut instruction = instruction_set.decode("jmp -3").unwrap();
+
+        let result_1 = instruction.change_operation(instruction_set.get_operation("nop").unwrap());
+        let result_2 = instruction.change_operation(instruction_set.get_operation("jz").unwrap());
+
+        assert_eq!(result_1, Ok(()));
+        assert_eq!(instruction.to_string(), "nop -3");
+        assert!(result_2.is_err());
+        assert_eq!(instruction.to_string(), "nop -3");
+    }
+
+    #[test]
+    fn test_instruction_execute_overflow() {
+        let instruction_set = InstructionSet::extended();
+
+        let mut registers: Registers = vec![
+            (String::from("a"), i64::MAX),
+            (String::from(ACCUMULATOR), i64::MAX),
+        ]
+        .into_iter()
+        .collect();
+
+        for info in &["add a 1", "acc 1", "mul a 2"] {
+            instruction_set
+                .decode(info)
+                .unwrap()
+                .execute(&mut registers);
+        }
+
+        assert_eq!(read_register(&registers, "a"), 0);
+        assert_eq!(read_register(&registers, ACCUMULATOR), i64::MIN);
+    }
+
+    #[test]
+    fn test_instruction_set_with_operation() {
+        let instruction_set = InstructionSet::handheld()
+            .with_operation(Operation::new("dbl", 0, |_, registers| {
+                *registers.entry(String::from(ACCUMULATOR)).or_insert(0) *= 2;
+
+                1
+            }))
+            .with_operation(Operation::new("hop", 0, |_, _| 2));
+
+        let mut registers: Registers = vec![(String::from(ACCUMULATOR), 21)].into_iter().collect();
+
+        let result_1 = instruction_set
+            .decode("dbl")
+            .unwrap()
+            .execute(&mut registers);
+        let result_2 = instruction_set
+            .decode("hop")
+            .unwrap()
+            .execute(&mut registers);
+
+        assert_eq!(result_1, 1);
+        assert_eq!(result_2, 1);
+        assert_eq!(read_register(&registers, ACCUMULATOR), 42);
+    }
+
+    #[test]
+    fn test_instruction_display() {
+        let instruction_set = InstructionSet::extended();
+
+        assert_eq!(
+            instruction_set.decode("acc -99").unwrap().to_string(),
+            "acc -99"
+        );
+        assert_eq!(
+            instruction_set.decode("nop 0").unwrap().to_string(),
+            "nop +0"
+        );
+        assert_eq!(
+            instruction_set.decode("jnz a,-2").unwrap().to_string(),
+            "jnz a -2"
+        );
+    }
+}
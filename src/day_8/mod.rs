@@ -1,7 +1,22 @@
+mod analysis;
+mod assembler;
+mod debugger;
+mod disassembler;
 mod handheld;
+mod instruction;
+mod snapshot;
+#[cfg(test)]
+mod test_utils;
+
+use std::fs;
 
 use crate::util::file_reader::to_string_vector;
+use crate::{get_user_input, wants_extras};
+use disassembler::Disassembler;
 use handheld::BootCode;
+use instruction::InstructionSet;
+
+const STEP_LIMIT: usize = 1_000_000;
 
 pub fn run_day_8() {
     let file_input = to_string_vector("inputs/day_8.txt");
@@ -16,13 +31,56 @@ pub fn run_day_8() {
 
             boot_code.reset();
 
+            let candidate_fixes = boot_code.find_corrupted_instructions();
+
             boot_code.execute_self_correcting();
 
             let part_2 = boot_code.get_accumulator();
 
             println!("Day 8 Part 1: {}", part_1);
             println!("Day 8 Part 2: {}", part_2);
+            println!("Day 8 Candidate Fixes: {:?}", candidate_fixes);
+
+            if !wants_extras() {
+                return;
+            }
+
+            print!("Disassemble the boot code (0 = no, 1 = listing, 2 = graphviz dot): ");
+
+            let original_boot_code = BootCode::new(&input_lines);
+            let disassembler = Disassembler::new(original_boot_code.get_instructions());
+
+            match get_user_input() {
+                1 => print!("{}", disassembler.listing()),
+                2 => print!("{}", disassembler.to_dot()),
+                _ => {}
+            }
+
+            print!("Debug a program (0 = no, 1 = boot code, 2 = assembly file): ");
+
+            match get_user_input() {
+                1 => debugger::run_debugger(&mut BootCode::new(&input_lines)),
+                2 => debug_assembly_file(),
+                _ => {}
+            }
         }
         Err(error) => println!("Error Parsing File: {:?}", error),
     };
 }
+
+fn debug_assembly_file() {
+    print!("Path to the assembly file: ");
+
+    let path = debugger::read_line();
+
+    let boot_code = fs::read_to_string(path.trim())
+        .map_err(|error| format!("Cannot Read {}: {}", path.trim(), error))
+        .map(|source| source.lines().map(String::from).collect::<Vec<String>>())
+        .and_then(|source| BootCode::assemble(&source, &InstructionSet::extended()))
+        .map(|boot_code| boot_code.with_step_limit(STEP_LIMIT));
+
+    match boot_code {
+        Ok(mut boot_code) => debugger::run_debugger(&mut boot_code),
+        Err(error) => println!("Error Assembling File: {:?}", error),
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::handheld::{Outcome, Status};
+use super::instruction::{read_register, Instruction, InstructionSet, Registers};
+
+/// The state of a `BootCode` program at one point of its execution.
+///
+/// A snapshot prints as one `key value` line per entry and `Snapshot::parse` reads that text
+/// back. The trace and the states seen so far are not part of it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Snapshot {
+    pub instructions: Vec<Instruction>,
+    pub current_instruction_index: usize,
+    pub registers: Registers,
+    pub status: Status,
+}
+
+impl Snapshot {
+    pub fn parse(lines: &[String], instruction_set: &InstructionSet) -> Result<Snapshot, String> {
+        let mut instructions = Vec::new();
+        let mut current_instruction_index = None;
+        let mut registers = Registers::new();
+        let mut status = None;
+
+        for (line_index, line) in lines.iter().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+
+            let result = match key {
+                "pc" => parse_number(value).map(|value| current_instruction_index = Some(value)),
+                "status" => parse_status(value).map(|value| status = Some(value)),
+                "register" => parse_register(value).map(|(name, value)| {
+                    registers.insert(name, value);
+                }),
+                "instruction" => instruction_set
+                    .decode(value)
+                    .map(|instruction| instructions.push(instruction)),
+                _ => Err(format!("Unknown Entry: {:?}", key)),
+            };
+
+            result.map_err(|error| format!("Line {}: {}", line_index + 1, error))?;
+        }
+
+        Ok(Snapshot {
+            instructions,
+            current_instruction_index: current_instruction_index.ok_or("Missing pc")?,
+            registers,
+            status: status.ok_or("Missing status")?,
+        })
+    }
+
+    /// Describes every difference from `self` to `other`, one change per line.
+    pub fn diff(&self, other: &Snapshot) -> Vec<String> {
+        let mut differences = Vec::new();
+
+        if self.current_instruction_index != other.current_instruction_index {
+            differences.push(format!(
+                "pc: {} -> {}",
+                self.current_instruction_index, other.current_instruction_index
+            ));
+        }
+
+        if self.status != other.status {
+            differences.push(format!(
+                "status: {} -> {}",
+                format_status(&self.status),
+                format_status(&other.status)
+            ));
+        }
+
+        let register_names: BTreeSet<&String> = self
+            .registers
+            .keys()
+            .chain(other.registers.keys())
+            .collect();
+
+        for name in register_names {
+            let old_value = read_register(&self.registers, name);
+            let new_value = read_register(&other.registers, name);
+
+            if old_value != new_value {
+                differences.push(format!("register {}: {} -> {}", name, old_value, new_value));
+            }
+        }
+
+        let number_of_instructions = self.instructions.len().max(other.instructions.len());
+
+        for index in 0..number_of_instructions {
+            let old_instruction = self.instructions.get(index);
+            let new_instruction = other.instructions.get(index);
+
+            if old_instruction != new_instruction {
+                differences.push(format!(
+                    "instruction {}: {} -> {}",
+                    index,
+                    old_instruction.map_or(String::from("<none>"), |i| i.to_string()),
+                    new_instruction.map_or(String::from("<none>"), |i| i.to_string())
+                ));
+            }
+        }
+
+        differences
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "pc {}", self.current_instruction_index)?;
+        writeln!(f, "status {}", format_status(&self.status))?;
+
+        for (name, value) in &self.registers {
+            writeln!(f, "register {} {}", name, value)?;
+        }
+
+        for instruction in &self.instructions {
+            writeln!(f, "instruction {}", instruction)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn format_status(status: &Status) -> String {
+    match status {
+        Status::Initialized => String::from("initialized"),
+        Status::Working => String::from("working"),
+        Status::Stopped(Outcome::Terminated) => String::from("terminated"),
+        Status::Stopped(Outcome::LoopDetected { entry_index, cycle }) => {
+            let cycle: Vec<String> = cycle.iter().map(|index| index.to_string()).collect();
+
+            format!("loop {} {}", entry_index, cycle.join(","))
+        }
+        Status::Stopped(Outcome::OutOfBounds {
+            instruction_index,
+            target,
+        }) => format!("out_of_bounds {} {}", instruction_index, target),
+        Status::Stopped(Outcome::StepLimitExceeded { step_limit }) => {
+            format!("step_limit {}", step_limit)
+        }
+    }
+}
+
+fn parse_status(status_info: &str) -> Result<Status, String> {
+    let tokens: Vec<&str> = status_info.split_whitespace().collect();
+
+    let status = match tokens.as_slice() {
+        ["initialized"] => Status::Initialized,
+        ["working"] => Status::Working,
+        ["terminated"] => Status::Stopped(Outcome::Terminated),
+        ["loop", entry_index, cycle] => Status::Stopped(Outcome::LoopDetected {
+            entry_index: parse_number(entry_index)?,
+            cycle: cycle
+                .split(',')
+                .map(parse_number)
+                .collect::<Result<Vec<usize>, String>>()?,
+        }),
+        ["out_of_bounds", instruction_index, target] => Status::Stopped(Outcome::OutOfBounds {
+            instruction_index: parse_number(instruction_index)?,
+            target: parse_number(target)?,
+        }),
+        ["step_limit", step_limit] => Status::Stopped(Outcome::StepLimitExceeded {
+            step_limit: parse_number(step_limit)?,
+        }),
+        _ => return Err(format!("Can't Parse Status: {:?}", status_info)),
+    };
+
+    Ok(status)
+}
+
+fn parse_register(register_info: &str) -> Result<(String, i64), String> {
+    match register_info
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        [name, value] => Ok((name.to_string(), parse_number(value)?)),
+        _ => Err(format!("Can't Parse Register: {:?}", register_info)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(number_info: &str) -> Result<T, String> {
+    number_info
+        .parse()
+        .map_err(|_| format!("Can't Parse Number: {:?}", number_info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_8::handheld::BootCode;
+    use crate::day_8::test_utils::TEST_DATA;
+
+    const TEST_SNAPSHOT: [&str; 6] = [
+        "pc 1",
+        "status loop 1 1,2,3",
+        "register acc 5",
+        "instruction acc +1",
+        "instruction jmp -1",
+        "instruction nop +0",
+    ];
+
+    #[test]
+    fn test_snapshot_display_and_parse() {
+        let input: Vec<String> = TEST_SNAPSHOT.iter().map(|s| s.to_string()).collect();
+
+        let snapshot = Snapshot::parse(&input, &InstructionSet::handheld()).unwrap();
+
+        let expected_status = Status::Stopped(Outcome::LoopDetected {
+            entry_index: 1,
+            cycle: vec![1, 2, 3],
+        });
+
+        assert_eq!(snapshot.current_instruction_index, 1);
+        assert_eq!(snapshot.status, expected_status);
+        assert_eq!(read_register(&snapshot.registers, "acc"), 5);
+        assert_eq!(snapshot.instructions.len(), 3);
+        assert_eq!(snapshot.to_string(), TEST_SNAPSHOT.join("\n") + "\n");
+    }
+
+    #[test]
+    fn test_snapshot_parse_errors() {
+        let parse_lines = |lines: &[&str]| {
+            let input: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+
+            Snapshot::parse(&input, &InstructionSet::handheld())
+        };
+
+        assert_eq!(
+            parse_lines(&["pc 0", "status done"]),
+            Err(String::from("Line 2: Can't Parse Status: \"done\""))
+        );
+        assert_eq!(
+            parse_lines(&["pc 0", "status working", "memory 5"]),
+            Err(String::from("Line 3: Unknown Entry: \"memory\""))
+        );
+        assert_eq!(
+            parse_lines(&["status working"]),
+            Err(String::from("Missing pc"))
+        );
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut boot_code = BootCode::new(&input);
+
+        let before = boot_code.snapshot();
+
+        boot_code.execute_self_correcting();
+
+        let result = before.diff(&boot_code.snapshot());
+
+        let expected = vec![
+            String::from("pc: 0 -> 9"),
+            String::from("status: initialized -> terminated"),
+            String::from("register acc: 0 -> 8"),
+            String::from("instruction 7: jmp -4 -> nop -4"),
+        ];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_boot_code_restore() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let mut boot_code = BootCode::new(&input);
+
+        boot_code.step();
+        boot_code.step();
+
+        let snapshot = boot_code.snapshot();
+
+        boot_code.execute_self_correcting();
+        boot_code.restore(&snapshot);
+
+        assert_eq!(boot_code.snapshot(), snapshot);
+        assert_eq!(boot_code.get_accumulator(), 1);
+        assert!(boot_code.get_trace().is_empty());
+        assert_eq!(
+            boot_code.execute(),
+            Outcome::LoopDetected {
+                entry_index: 2,
+                cycle: vec![2, 6, 7, 3, 4, 1]
+            }
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use super::assembler::assemble;
+use super::instruction::{Instruction, InstructionSet};
+
+/// The example boot code from the puzzle, which loops before reaching its last instruction.
+pub const TEST_DATA: [&str; 9] = [
+    "nop +0", "acc +1", "jmp +4", "acc +3", "jmp -3", "acc -99", "acc +1", "jmp -4", "acc +6",
+];
+
+pub fn get_instructions(lines: &[&str]) -> Vec<Instruction> {
+    let source: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+
+    assemble(&source, &InstructionSet::extended()).unwrap()
+}
@@ -1,6 +1,10 @@
+mod validator;
+mod weakness;
 mod xmas;
 
 use crate::util::file_reader::to_string_vector;
+use crate::wants_extras;
+use weakness::WeaknessDefinition;
 use xmas::Xmas;
 
 pub fn run_day_9() {
@@ -15,6 +19,31 @@ pub fn run_day_9() {
 
             println!("Day 9 Part 1: {:?}", part_1);
             println!("Day 9 Part 2: {:?}", part_2);
+
+            if !wants_extras() {
+                return;
+            }
+
+            println!(
+                "Day 9 Invalid Elements (position, value): {:?}",
+                xmas.find_invalid_elements()
+            );
+
+            if let Some(weak_range) = xmas.find_weak_range() {
+                println!("Day 9 Weak Range: {}", weak_range);
+                println!(
+                    "Day 9 Weakness (first + last): {:?}",
+                    weak_range.weakness(WeaknessDefinition::FirstPlusLast)
+                );
+            }
+
+            if let Some(invalid_element) = part_1 {
+                println!(
+                    "Day 9 Ranges Summing To {}: {}",
+                    invalid_element,
+                    xmas.find_ranges_that_sum_to(invalid_element, 2).len()
+                );
+            }
         }
         Err(error) => println!("Error Parsing File: {:?}", error),
     };
@@ -0,0 +1,132 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Checks XMAS values one at a time against the `window_size` values received before them.
+///
+/// A value is valid if two different values of the window sum to it. The first `window_size`
+/// values only fill the window and are always valid. The sums of all pairs in the window are
+/// counted as values come and go, so checking a value is a single lookup.
+#[derive(Debug, PartialEq)]
+pub struct XmasValidator {
+    window_size: usize,
+    window: VecDeque<u64>,
+    pair_sums: HashMap<u64, usize>,
+    position: usize,
+}
+
+impl XmasValidator {
+    pub fn new(window_size: usize) -> XmasValidator {
+        XmasValidator {
+            window_size,
+            window: VecDeque::with_capacity(window_size + 1),
+            pair_sums: HashMap::new(),
+            position: 0,
+        }
+    }
+
+    /// Checks the next value and moves the window past it.
+    pub fn push(&mut self, value: u64) -> bool {
+        let is_valid =
+            self.position < self.window_size || self.pair_sums.get(&value).is_some_and(|&n| 0 < n);
+
+        for &other_value in self.window.iter().filter(|&&other| other != value) {
+            *self.pair_sums.entry(value + other_value).or_insert(0) += 1;
+        }
+
+        self.window.push_back(value);
+        self.position += 1;
+
+        if self.window_size < self.window.len() {
+            self.evict_oldest();
+        }
+
+        is_valid
+    }
+
+    /// Lazily validates `values`, yielding the position and value of every invalid one.
+    pub fn invalid_values<I>(mut self, values: I) -> impl Iterator<Item = (usize, u64)>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        values.into_iter().filter_map(move |value| {
+            let position = self.position;
+
+            if self.push(value) {
+                None
+            } else {
+                Some((position, value))
+            }
+        })
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(oldest_value) = self.window.pop_front() {
+            for &other_value in self.window.iter().filter(|&&other| other != oldest_value) {
+                let sum = oldest_value + other_value;
+
+                if let Some(count) = self.pair_sums.get_mut(&sum) {
+                    *count -= 1;
+
+                    if *count == 0 {
+                        self.pair_sums.remove(&sum);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_DATA: [u64; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn test_xmas_validator_push() {
+        let mut validator = XmasValidator::new(2);
+
+        let result: Vec<bool> = vec![1, 2, 3, 5, 9, 14, 14]
+            .into_iter()
+            .map(|value| validator.push(value))
+            .collect();
+
+        let expected = vec![true, true, true, true, false, true, false];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_xmas_validator_invalid_values() {
+        let result: Vec<(usize, u64)> = XmasValidator::new(5)
+            .invalid_values(TEST_DATA.iter().copied())
+            .collect();
+
+        let expected = vec![(14, 127)];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_xmas_validator_invalid_values_within_second_window() {
+        let result: Vec<(usize, u64)> = XmasValidator::new(2)
+            .invalid_values(vec![1, 2, 3, 10, 13, 20])
+            .collect();
+
+        let expected = vec![(3, 10), (5, 20)];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_xmas_validator_needs_different_values() {
+        let result: Vec<(usize, u64)> = XmasValidator::new(3)
+            .invalid_values(vec![5, 5, 1, 10, 6, 10])
+            .collect();
+
+        let expected = vec![(3, 10), (5, 10)];
+
+        assert_eq!(result, expected);
+    }
+}
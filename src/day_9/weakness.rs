@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// How to turn a contiguous range into the encryption weakness.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WeaknessDefinition {
+    /// The puzzle's definition: the smallest plus the largest value of the range.
+    SmallestPlusLargest,
+    FirstPlusLast,
+}
+
+/// A run of consecutive values, with the indices it covers in the data.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContiguousRange {
+    pub range: Range<usize>,
+    pub values: Vec<u64>,
+}
+
+impl ContiguousRange {
+    pub fn weakness(&self, definition: WeaknessDefinition) -> Option<u64> {
+        match definition {
+            WeaknessDefinition::SmallestPlusLargest => {
+                Some(self.values.iter().min()? + self.values.iter().max()?)
+            }
+            WeaknessDefinition::FirstPlusLast => Some(self.values.first()? + self.values.last()?),
+        }
+    }
+}
+
+impl fmt::Display for ContiguousRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}..{} ({} values)",
+            self.range.start,
+            self.range.end,
+            self.values.len()
+        )
+    }
+}
+
+/// Running totals of `data`; element `i` is the sum of the first `i` values.
+pub fn prefix_sums(data: &[u64]) -> Vec<u64> {
+    let mut prefix_sums = Vec::with_capacity(data.len() + 1);
+
+    prefix_sums.push(0);
+
+    for value in data {
+        prefix_sums.push(prefix_sums.last().unwrap() + value);
+    }
+
+    prefix_sums
+}
+
+/// Every range of at least `minimum_length` values that sums to `target`, ordered by start
+/// and then by end.
+pub fn find_ranges_that_sum_to(
+    data: &[u64],
+    target: u64,
+    minimum_length: usize,
+) -> Vec<ContiguousRange> {
+    let prefix_sums = prefix_sums(data);
+
+    let mut starts_by_prefix_sum: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut ranges = Vec::new();
+
+    for (end, &prefix_sum) in prefix_sums.iter().enumerate() {
+        if minimum_length <= end {
+            let last_start = end - minimum_length;
+
+            starts_by_prefix_sum
+                .entry(prefix_sums[last_start])
+                .or_default()
+                .push(last_start);
+        }
+
+        let starts = prefix_sum
+            .checked_sub(target)
+            .and_then(|start_sum| starts_by_prefix_sum.get(&start_sum));
+
+        for &start in starts.into_iter().flatten() {
+            ranges.push(ContiguousRange {
+                range: start..end,
+                values: data[start..end].to_vec(),
+            });
+        }
+    }
+
+    ranges.sort_by_key(|range| (range.range.start, range.range.end));
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_DATA: [u64; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn test_prefix_sums() {
+        let result = prefix_sums(&[3, 0, 4, 1]);
+
+        let expected = vec![0, 3, 3, 7, 8];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_find_ranges_that_sum_to() {
+        let result = find_ranges_that_sum_to(&TEST_DATA, 127, 2);
+
+        let expected = vec![ContiguousRange {
+            range: 2..6,
+            values: vec![15, 25, 47, 40],
+        }];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_find_ranges_that_sum_to_minimum_length() {
+        let data = [5, 0, 5, 3, 2, 5];
+
+        let result: Vec<Range<usize>> = find_ranges_that_sum_to(&data, 5, 1)
+            .into_iter()
+            .map(|range| range.range)
+            .collect();
+
+        let expected = vec![0..1, 0..2, 1..3, 2..3, 3..5, 5..6];
+
+        assert_eq!(result, expected);
+
+        let result: Vec<Range<usize>> = find_ranges_that_sum_to(&data, 5, 2)
+            .into_iter()
+            .map(|range| range.range)
+            .collect();
+
+        let expected = vec![0..2, 1..3, 3..5];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_contiguous_range_weakness() {
+        let range = ContiguousRange {
+            range: 2..6,
+            values: vec![15, 25, 47, 40],
+        };
+
+        assert_eq!(
+            range.weakness(WeaknessDefinition::SmallestPlusLargest),
+            Some(62)
+        );
+        assert_eq!(range.weakness(WeaknessDefinition::FirstPlusLast), Some(55));
+        assert_eq!(range.to_string(), "2..6 (4 values)");
+    }
+}
@@ -1,6 +1,5 @@
-use std::ops::Range;
-
-use crate::util::sum_finder::find_two_values_that_sum_to;
+use super::validator::XmasValidator;
+use super::weakness::{find_ranges_that_sum_to, ContiguousRange, WeaknessDefinition};
 
 #[derive(Debug, PartialEq)]
 pub struct Xmas {
@@ -17,69 +16,37 @@ impl Xmas {
     }
 
     pub fn find_first_invalid_element(&self) -> Option<u64> {
-        let mut result = None;
-
-        let mut window_start = self.preamble_length;
-        let mut window_end = window_start + self.preamble_length;
-        let mut target_index = window_end;
-
-        while let Some(temp_data_slice) = self.data.get(window_start..window_end) {
-            if let Some(&target) = self.data.get(target_index) {
-                let find_sum_result = find_two_values_that_sum_to(temp_data_slice, target);
-
-                if find_sum_result.is_none() {
-                    result = Some(target);
-
-                    break;
-                }
-            } else {
-                break;
-            }
-
-            window_start += 1;
-            window_end += 1;
-            target_index += 1;
-        }
+        self.find_invalid_elements()
+            .first()
+            .map(|&(_, invalid_value)| invalid_value)
+    }
 
-        result
+    /// Positions and values of every element that isn't the sum of two of the
+    /// `preamble_length` elements right before it.
+    pub fn find_invalid_elements(&self) -> Vec<(usize, u64)> {
+        XmasValidator::new(self.preamble_length)
+            .invalid_values(self.data.iter().copied())
+            .collect()
     }
 
     pub fn find_encryption_weakness(&self) -> Option<u64> {
-        let first_invalid_element = self.find_first_invalid_element();
-        let mut result = None;
-
-        if let Some(invalid_value) = first_invalid_element {
-            let mut result_slice_range: Option<Range<usize>> = None;
-            let mut start_index = 0;
-            let mut end_index = 0;
-
-            let mut temp_sum = 0;
-
-            while start_index < self.data.len() && end_index < self.data.len() {
-                if temp_sum < invalid_value {
-                    temp_sum += *self.data.get(end_index).unwrap_or(&0);
-
-                    end_index += 1
-                } else if (temp_sum == invalid_value) && (2 <= end_index - start_index) {
-                    result_slice_range = Some(start_index..end_index);
-
-                    break;
-                } else {
-                    temp_sum -= *self.data.get(start_index).unwrap_or(&0);
-
-                    start_index += 1
-                }
-            }
-
-            if let Some(range) = result_slice_range {
-                let result_slice = self.data.get(range).unwrap();
+        self.find_weak_range()?
+            .weakness(WeaknessDefinition::SmallestPlusLargest)
+    }
 
-                result =
-                    Some(result_slice.iter().min().unwrap() + result_slice.iter().max().unwrap());
-            }
-        }
+    /// The first range of at least two elements that sums to the first invalid element.
+    pub fn find_weak_range(&self) -> Option<ContiguousRange> {
+        self.find_ranges_that_sum_to(self.find_first_invalid_element()?, 2)
+            .into_iter()
+            .next()
+    }
 
-        result
+    pub fn find_ranges_that_sum_to(
+        &self,
+        target: u64,
+        minimum_length: usize,
+    ) -> Vec<ContiguousRange> {
+        find_ranges_that_sum_to(&self.data, target, minimum_length)
     }
 }
 
@@ -119,6 +86,22 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_find_first_invalid_element_within_second_preamble() {
+        let input: Vec<String> = vec!["1", "2", "3", "10", "13"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let xmas = Xmas::new(&input, 2);
+
+        let result = xmas.find_first_invalid_element();
+
+        let expected = Some(10);
+
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn test_find_encryption_weakness() {
         let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
@@ -131,4 +114,20 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_find_weak_range() {
+        let input: Vec<String> = TEST_DATA.iter().map(|s| s.to_string()).collect();
+
+        let xmas = Xmas::new(&input, 5);
+
+        let result = xmas.find_weak_range();
+
+        let expected = Some(ContiguousRange {
+            range: 2..6,
+            values: vec![15, 25, 47, 40],
+        });
+
+        assert_eq!(result, expected);
+    }
 }
@@ -20,6 +20,7 @@ mod day_7;
 mod day_8;
 mod day_9;
 
+use std::convert::TryFrom;
 use std::io::{self, Write};
 
 fn print_seperator() {
@@ -66,6 +67,23 @@ pub fn get_user_input() -> u32 {
         .expect("Failed to parse user_input!")
 }
 
+/// Asks once whether to run the optional extras of a day, after its answers are printed.
+pub fn wants_extras() -> bool {
+    print!("Explore the extras (0 = no, 1 = yes): ");
+
+    get_user_input() == 1
+}
+
+/// Like `get_user_input`, but asks again until the number fits in an `i32`.
+pub fn get_i32_user_input() -> i32 {
+    loop {
+        match i32::try_from(get_user_input()) {
+            Ok(input) => return input,
+            Err(_) => print!("Please enter a number up to {}: ", i32::MAX),
+        }
+    }
+}
+
 fn main() {
     print_seperator();
 
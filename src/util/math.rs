@@ -8,9 +8,7 @@ where
     let mut min = second;
 
     if min > max {
-        let val = max;
-        max = min;
-        min = val;
+        std::mem::swap(&mut max, &mut min);
     }
 
     loop {
@@ -33,3 +31,75 @@ where
 {
     first * (second / gcd(first, second))
 }
+
+/// `(g, x, y)` with `a * x + b * y == g`, where `g` is the greatest common divisor.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Solves `x % modulus == remainder` for every `(remainder, modulus)` pair with the chinese
+/// remainder theorem. The moduli do not have to be coprime. Returns the smallest non-negative
+/// solution and the period after which the solutions repeat, or `None` if the congruences
+/// contradict each other or the period does not fit in a `u64`.
+pub fn solve_congruences(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut solution: i128 = 0;
+    let mut period: i128 = 1;
+
+    for &(remainder, modulus) in congruences {
+        if modulus == 0 {
+            return None;
+        }
+
+        let (remainder, modulus) = (i128::from(remainder % modulus), i128::from(modulus));
+
+        let (g, inverse, _) = extended_gcd(period, modulus);
+
+        if (remainder - solution) % g != 0 {
+            return None;
+        }
+
+        let reduced_modulus = modulus / g;
+
+        // Both factors are below `reduced_modulus`, a u64, so their product fits in a u128.
+        let step = ((remainder - solution) / g).rem_euclid(reduced_modulus) as u128
+            * inverse.rem_euclid(reduced_modulus) as u128
+            % reduced_modulus as u128;
+
+        let next_period = (period / g)
+            .checked_mul(modulus)
+            .filter(|&next_period| next_period <= i128::from(u64::MAX))?;
+
+        solution += period * step as i128;
+        period = next_period;
+    }
+
+    Some((solution as u64, period as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_congruences() {
+        let result_1 = solve_congruences(&[(0, 17), (11, 13), (16, 19)]);
+        let result_2 = solve_congruences(&[(2, 4), (8, 6)]);
+        let result_3 = solve_congruences(&[(1, 4), (2, 6)]);
+        let result_4 = solve_congruences(&[]);
+        let result_5 = solve_congruences(&[(0, u64::MAX), (1, u64::MAX - 1)]);
+        let result_6 = solve_congruences(&[(123456789, 4294967291), (987654321, 4294967279)]);
+
+        assert_eq!(result_1, Some((3417, 4199)));
+        assert_eq!(result_2, Some((2, 12)));
+        assert_eq!(result_3, None);
+        assert_eq!(result_4, Some((0, 1)));
+        assert_eq!(result_5, None);
+        assert_eq!(result_6, Some((309308344532033940, 18446743979220271189)));
+    }
+}